@@ -1,12 +1,11 @@
 use std::time::Duration;
 
 use gpui::{
-    AnyElement, App, AppContext, Application, Bounds, Context, ElementId, Fill, Hsla, KeyBinding,
-    Menu, Rgba, TitlebarOptions, Window, WindowBounds, WindowOptions, actions, div, ease_in_out,
-    ease_out_quint, point, prelude::*, px, rgb, size,
+    AnyElement, App, AppContext, Application, Bounds, Context, ElementId, Hsla, KeyBinding, Menu,
+    Rgba, TitlebarOptions, Window, WindowBounds, WindowOptions, actions, div, ease_in_out,
+    ease_out_quint, hsla, point, prelude::*, px, rgb, size,
 };
-use gpui_transitions::{Lerp, WindowUseTransition};
-use palette::{FromColor, Hsl, IntoColor, Mix, Srgb};
+use gpui_transitions::{Lerp, Oklab, WindowUseTransition};
 use rand::Rng;
 use smallvec::SmallVec;
 
@@ -144,52 +143,18 @@ fn random_pastel_hex(prev_hue: Option<f32>) -> Rgba {
 
     let hue = match prev_hue {
         Some(prev_hue) => loop {
-            let hue = rng.random_range(0.0..360.0);
-            let diff = (hue - prev_hue).abs() % 360.0; // wrap-around
-            if diff > 120.0 && diff < 300.0 {
+            let hue = rng.random_range(0.0..1.0);
+            let diff = (hue - prev_hue).abs() % 1.0; // wrap-around
+            if diff > 1. / 3. && diff < 5. / 6. {
                 break hue;
             }
         },
 
-        None => rng.random_range(0.0..360.0),
+        None => rng.random_range(0.0..1.0),
     };
 
     let s = rng.random_range(0.7..0.95);
     let l = rng.random_range(0.7..0.8);
 
-    let rgb_f32: Srgb<f32> = Hsl::new(hue, s, l).into_color();
-    let rgb_u8: Srgb<u8> = rgb_f32.into_format();
-    let hex = ((rgb_u8.red as u32) << 16) | ((rgb_u8.green as u32) << 8) | (rgb_u8.blue as u32);
-
-    rgb(hex)
-}
-
-/// A simple wrapper around Rgba that implements a more perceptual lerp via Oklab.
-#[derive(PartialEq, Clone, Copy)]
-struct Oklab(Rgba);
-
-impl Lerp for Oklab {
-    fn lerp(&self, to: &Self, delta: f32) -> Self {
-        let self_srgba = palette::Srgba::new(self.0.r, self.0.g, self.0.b, self.0.a);
-        let to_srgba = palette::Srgba::new(to.0.r, to.0.g, to.0.b, to.0.a);
-
-        let self_oklab: palette::Oklab = palette::Oklab::from_color(self_srgba);
-        let to_oklab: palette::Oklab = palette::Oklab::from_color(to_srgba);
-
-        let lerped_oklab = self_oklab.mix(to_oklab, delta);
-        let lerped_srgba: palette::Srgba<f32> = palette::Srgba::from_color(lerped_oklab);
-
-        Oklab(gpui::Rgba {
-            r: lerped_srgba.red,
-            g: lerped_srgba.green,
-            b: lerped_srgba.blue,
-            a: lerped_srgba.alpha,
-        })
-    }
-}
-
-impl Into<Fill> for Oklab {
-    fn into(self) -> Fill {
-        self.0.into()
-    }
+    hsla(hue, s, l, 1.).into()
 }
@@ -3,31 +3,13 @@ use std::time::Duration;
 use gpui::{
     AnyElement, App, AppContext, Application, Bounds, Context, Div, ElementId, Interactivity,
     KeyBinding, Menu, StyleRefinement, TitlebarOptions, Window, WindowBounds, WindowOptions,
-    actions, div, ease_in_out, ease_out_quint, point, prelude::*, px, rgb, size,
+    actions, div, ease_in_out, point, prelude::*, px, rgb, size,
 };
-use gpui_transitions::{Lerp, WindowUseTransition};
+use gpui_transitions::{Lerp, WindowUseTransition, easing};
 use smallvec::SmallVec;
 
 actions!(app, [Quit]);
 
-pub fn ease_out_bounce(t: f32) -> f32 {
-    let n1 = 7.5625;
-    let d1 = 2.75;
-
-    if t < 1.0 / d1 {
-        n1 * t * t
-    } else if t < 2.0 / d1 {
-        let t = t - 1.5 / d1;
-        n1 * t * t + 0.75
-    } else if t < 2.5 / d1 {
-        let t = t - 2.25 / d1;
-        n1 * t * t + 0.9375
-    } else {
-        let t = t - 2.625 / d1;
-        n1 * t * t + 0.984375
-    }
-}
-
 #[derive(IntoElement)]
 struct Button {
     id: ElementId,
@@ -150,13 +132,13 @@ impl Render for Root {
             .use_keyed_transition("slide", cx, Duration::from_millis(500), |_window, _cx| {
                 self.button1_location.position()
             })
-            .with_easing(ease_out_quint());
+            .with_easing(easing::ease_out_quint);
 
         let slide_transition2 = window
             .use_keyed_transition("bounce", cx, Duration::from_millis(800), |_window, _cx| {
                 Location::Top.position()
             })
-            .with_easing(ease_out_bounce);
+            .with_easing(easing::ease_out_bounce);
 
         let left_btn_pos = *slide_transition1.evaluate(window, cx);
         let right_btn_pos = *slide_transition2.evaluate(window, cx);
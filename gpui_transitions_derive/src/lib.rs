@@ -0,0 +1,270 @@
+//! Derive macros for [gpui_transitions](https://docs.rs/gpui_transitions).
+//!
+//! These are re-exported by `gpui_transitions` when its `derive` feature is
+//! enabled, and should be used through that crate.
+
+use proc_macro::TokenStream;
+use proc_macro2::{TokenStream as TokenStream2, TokenTree};
+use quote::{ToTokens, format_ident, quote};
+use syn::{
+    Data, DeriveInput, Fields, GenericParam, Ident, Index, Type, WherePredicate, parse_macro_input,
+    parse_quote, spanned::Spanned,
+};
+
+/// Derives `gpui_transitions::Lerp` for a struct or enum.
+///
+/// Every field is interpolated with its own `Lerp` implementation, unless it is
+/// marked with one of the following attributes:
+///
+/// - `#[lerp(skip)]` - The field is not animated. The value is always taken
+///   from the target.
+/// - `#[lerp(snap)]` - The field jumps from the start value to the target value
+///   halfway through the interpolation, like discrete properties in CSS.
+///
+/// Fields marked with either attribute only need to implement `Clone`.
+///
+/// For enums, two values of the same variant are interpolated field by field.
+/// Values of different variants snap halfway through, which requires the enum
+/// to implement `Clone`.
+#[proc_macro_derive(Lerp, attributes(lerp))]
+pub fn derive_lerp(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Mode {
+    Lerp,
+    Skip,
+    Snap,
+}
+
+fn field_mode(attrs: &[syn::Attribute]) -> syn::Result<Mode> {
+    let mut mode = Mode::Lerp;
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("lerp")) {
+        attr.parse_nested_meta(|meta| {
+            let new_mode = if meta.path.is_ident("skip") {
+                Mode::Skip
+            } else if meta.path.is_ident("snap") {
+                Mode::Snap
+            } else {
+                return Err(meta.error("expected `skip` or `snap`"));
+            };
+
+            if mode != Mode::Lerp {
+                return Err(meta.error("a field can only have one `lerp` attribute"));
+            }
+
+            mode = new_mode;
+            Ok(())
+        })?;
+    }
+
+    Ok(mode)
+}
+
+/// Returns whether `tokens` mention any of the given type parameters.
+fn mentions_any(tokens: TokenStream2, params: &[Ident]) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(ident) => params.contains(&ident),
+        TokenTree::Group(group) => mentions_any(group.stream(), params),
+        _ => false,
+    })
+}
+
+/// Generates the expression for a single field, given expressions referring to
+/// the field on the start and target values.
+fn field_expr(mode: Mode, from: &TokenStream2, to: &TokenStream2) -> TokenStream2 {
+    match mode {
+        Mode::Lerp => quote! { ::gpui_transitions::Lerp::lerp(#from, #to, delta) },
+        Mode::Skip => quote! { ::core::clone::Clone::clone(#to) },
+        Mode::Snap => quote! {
+            if delta < 0.5 {
+                ::core::clone::Clone::clone(#from)
+            } else {
+                ::core::clone::Clone::clone(#to)
+            }
+        },
+    }
+}
+
+struct FieldInfo {
+    mode: Mode,
+    ty: Type,
+    /// The name used to construct the field, e.g. `foo` or `0`.
+    member: TokenStream2,
+    /// The bindings used when destructuring the start and target values.
+    from_binding: Ident,
+    to_binding: Ident,
+}
+
+fn collect_fields(fields: &Fields) -> syn::Result<Vec<FieldInfo>> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| {
+            let member = match &field.ident {
+                Some(ident) => ident.to_token_stream(),
+                None => Index::from(index).to_token_stream(),
+            };
+
+            Ok(FieldInfo {
+                mode: field_mode(&field.attrs)?,
+                ty: field.ty.clone(),
+                member,
+                from_binding: format_ident!("__from_{}", index),
+                to_binding: format_ident!("__to_{}", index),
+            })
+        })
+        .collect()
+}
+
+/// Generates a pattern destructuring every field of `path` into the given bindings.
+fn pattern(
+    path: &TokenStream2,
+    fields: &Fields,
+    infos: &[FieldInfo],
+    binding: impl Fn(&FieldInfo) -> &Ident,
+) -> TokenStream2 {
+    let members = infos.iter().map(|info| &info.member);
+    let bindings = infos.iter().map(binding);
+
+    match fields {
+        Fields::Named(_) | Fields::Unnamed(_) => quote! { #path { #( #members: #bindings ),* } },
+        Fields::Unit => quote! { #path },
+    }
+}
+
+/// Generates an expression building `path` from the destructured bindings.
+fn construct(path: &TokenStream2, fields: &Fields, infos: &[FieldInfo]) -> TokenStream2 {
+    let members = infos.iter().map(|info| &info.member);
+    let values = infos.iter().map(|info| {
+        let from = info.from_binding.to_token_stream();
+        let to = info.to_binding.to_token_stream();
+        field_expr(info.mode, &from, &to)
+    });
+
+    match fields {
+        Fields::Named(_) | Fields::Unnamed(_) => quote! { #path { #( #members: #values ),* } },
+        Fields::Unit => quote! { #path },
+    }
+}
+
+fn expand(mut input: DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+
+    let type_params: Vec<Ident> = input
+        .generics
+        .params
+        .iter()
+        .filter_map(|param| match param {
+            GenericParam::Type(param) => Some(param.ident.clone()),
+            _ => None,
+        })
+        .collect();
+
+    let mut predicates: Vec<WherePredicate> = Vec::new();
+    let mut add_field_bounds = |infos: &[FieldInfo]| {
+        for info in infos {
+            // Fields that don't depend on a type parameter are checked by the
+            // compiler directly, so they don't need a bound.
+            if !mentions_any(info.ty.to_token_stream(), &type_params) {
+                continue;
+            }
+
+            let ty = &info.ty;
+            predicates.push(match info.mode {
+                Mode::Lerp => parse_quote! { #ty: ::gpui_transitions::Lerp },
+                Mode::Skip | Mode::Snap => parse_quote! { #ty: ::core::clone::Clone },
+            });
+        }
+    };
+
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let infos = collect_fields(&data.fields)?;
+            add_field_bounds(&infos);
+
+            let path = quote! { Self };
+            let from_pattern = pattern(&path, &data.fields, &infos, |info| &info.from_binding);
+            let to_pattern = pattern(&path, &data.fields, &infos, |info| &info.to_binding);
+            let construct = construct(&path, &data.fields, &infos);
+
+            quote! {
+                let #from_pattern = self;
+                let #to_pattern = to;
+                #construct
+            }
+        }
+
+        Data::Enum(data) => {
+            let mut arms = Vec::new();
+
+            for variant in &data.variants {
+                let infos = collect_fields(&variant.fields)?;
+                add_field_bounds(&infos);
+
+                let variant_name = &variant.ident;
+                let path = quote! { Self::#variant_name };
+                let from_pattern = pattern(&path, &variant.fields, &infos, |i| &i.from_binding);
+                let to_pattern = pattern(&path, &variant.fields, &infos, |i| &i.to_binding);
+                let construct = construct(&path, &variant.fields, &infos);
+
+                arms.push(quote! {
+                    (#from_pattern, #to_pattern) => #construct,
+                });
+            }
+
+            if data.variants.len() > 1 {
+                predicates.push(parse_quote! { Self: ::core::clone::Clone });
+
+                arms.push(quote! {
+                    _ => if delta < 0.5 {
+                        ::core::clone::Clone::clone(self)
+                    } else {
+                        ::core::clone::Clone::clone(to)
+                    },
+                });
+            }
+
+            if data.variants.is_empty() {
+                quote! { match *self {} }
+            } else {
+                quote! {
+                    match (self, to) {
+                        #( #arms )*
+                    }
+                }
+            }
+        }
+
+        Data::Union(data) => {
+            return Err(syn::Error::new(
+                data.union_token.span(),
+                "`Lerp` cannot be derived for unions",
+            ));
+        }
+    };
+
+    input
+        .generics
+        .make_where_clause()
+        .predicates
+        .extend(predicates);
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::gpui_transitions::Lerp for #name #ty_generics #where_clause {
+            #[allow(unused_variables)]
+            fn lerp(&self, to: &Self, delta: f32) -> Self {
+                #body
+            }
+        }
+    })
+}
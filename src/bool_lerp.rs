@@ -54,13 +54,18 @@ impl<N> BoolLerp<N>
 where
     N: PartialOrd + From<u8>,
 {
+    /// Creates a `BoolLerp` with the given value, clamped between 0 and 1 so that
+    /// overshooting easing functions can't push it past either state.
     pub(crate) fn new(n: N) -> Self {
-        debug_assert!(
-            n >= N::from(0) && n <= N::from(1),
-            "intermediate value must be between 0 and 1"
-        );
+        let value = if n < N::from(0) {
+            N::from(0)
+        } else if n > N::from(1) {
+            N::from(1)
+        } else {
+            n
+        };
 
-        BoolLerp { value: n }
+        BoolLerp { value }
     }
 }
 
@@ -187,6 +192,15 @@ mod tests {
         assert_eq!(three_quarter.value(), 0.75);
     }
 
+    #[test]
+    fn test_bool_lerp_overshoot_clamps() {
+        let start: BoolLerp<f32> = BoolLerp::falsey();
+        let end: BoolLerp<f32> = BoolLerp::truthy();
+
+        assert_eq!(start.lerp(&end, 1.2).value(), 1.0);
+        assert_eq!(start.lerp(&end, -0.2).value(), 0.0);
+    }
+
     #[test]
     fn test_bool_lerp_equality() {
         let a: BoolLerp<f32> = BoolLerp::truthy();
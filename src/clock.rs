@@ -0,0 +1,176 @@
+//! Time sources used to drive transitions.
+
+use std::{
+    cell::Cell,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use gpui::Window;
+
+/// A source of time for a [`TransitionState`](crate::TransitionState).
+///
+/// Transitions never read [`Instant::now`] directly. Instead they ask the clock
+/// stored in their state, which makes it possible to drive animations from a
+/// deterministic time source in tests or when replaying recorded sessions.
+///
+/// Three clocks are provided:
+/// - [`SystemClock`] - Reads the system's monotonic clock. This is the default.
+/// - [`ManualClock`] - Only moves when it is explicitly advanced.
+/// - [`FrameClock`] - Reports a single timestamp for the whole of a GPUI frame.
+pub trait Clock {
+    /// Returns the current time as seen by this clock.
+    fn now(&self) -> Instant;
+
+    /// Called by [`Transition::evaluate`](crate::Transition::evaluate) before the
+    /// transition is evaluated for the frame currently being drawn.
+    ///
+    /// Clocks that are tied to the frame lifecycle can use this to latch their
+    /// timestamp. The default implementation does nothing.
+    fn begin_frame(&self, _window: &Window) {}
+}
+
+/// A [`Clock`] that reads the system's monotonic clock.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// A [`Clock`] that only moves forward when it is explicitly advanced.
+///
+/// Clones share the same underlying time, so a clock handed to a
+/// [`TransitionState`](crate::TransitionState) can still be advanced from the
+/// outside.
+///
+/// # Example
+///
+/// ```ignore
+/// let clock = ManualClock::new();
+/// let state = cx.new(|_| TransitionState::new(0.0_f32).with_clock(clock.clone()));
+/// let transition = Transition::new(state, Duration::from_millis(100));
+///
+/// transition.update(cx, |val, _| *val = 100.0);
+/// clock.advance(Duration::from_millis(37));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ManualClock {
+    now: Rc<Cell<Instant>>,
+}
+
+impl ManualClock {
+    /// Creates a new manual clock starting at the current system time.
+    pub fn new() -> Self {
+        Self::starting_at(Instant::now())
+    }
+
+    /// Creates a new manual clock starting at the given instant.
+    pub fn starting_at(now: Instant) -> Self {
+        Self {
+            now: Rc::new(Cell::new(now)),
+        }
+    }
+
+    /// Moves the clock forward by the given duration.
+    pub fn advance(&self, duration: Duration) {
+        self.now.set(self.now.get() + duration);
+    }
+
+    /// Sets the clock to the given instant.
+    pub fn set(&self, now: Instant) {
+        self.now.set(now);
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        self.now.get()
+    }
+}
+
+/// A [`Clock`] driven by GPUI's frame lifecycle.
+///
+/// The first transition evaluated during a frame latches the current time, and
+/// every later read within the same frame returns that timestamp. The latch is
+/// released once the frame has been drawn. This keeps every transition that
+/// shares the clock in lockstep, no matter how long the frame takes to render.
+///
+/// Outside of a frame (e.g. in event handlers) the clock reads the system time.
+///
+/// Clones share the same underlying timestamp.
+#[derive(Debug, Default, Clone)]
+pub struct FrameClock {
+    frame_time: Rc<Cell<Option<Instant>>>,
+}
+
+impl FrameClock {
+    /// Creates a new frame clock.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Clock for FrameClock {
+    fn now(&self) -> Instant {
+        self.frame_time.get().unwrap_or_else(Instant::now)
+    }
+
+    fn begin_frame(&self, window: &Window) {
+        if self.frame_time.get().is_some() {
+            return;
+        }
+
+        self.frame_time.set(Some(Instant::now()));
+
+        let frame_time = self.frame_time.clone();
+        window.on_next_frame(move |_window, _cx| frame_time.set(None));
+    }
+}
+
+#[cfg(all(test, feature = "test-support"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manual_clock_does_not_move_on_its_own() {
+        let clock = ManualClock::new();
+        let first = clock.now();
+        std::thread::sleep(Duration::from_millis(2));
+        assert_eq!(clock.now(), first);
+    }
+
+    #[test]
+    fn test_manual_clock_advance() {
+        let start = Instant::now();
+        let clock = ManualClock::starting_at(start);
+
+        clock.advance(Duration::from_millis(37));
+        assert_eq!(clock.now(), start + Duration::from_millis(37));
+    }
+
+    #[test]
+    fn test_manual_clock_clones_share_time() {
+        let clock = ManualClock::new();
+        let cloned = clock.clone();
+
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(clock.now(), cloned.now());
+    }
+
+    #[test]
+    fn test_frame_clock_reads_system_time_outside_of_frame() {
+        let clock = FrameClock::new();
+        let first = clock.now();
+        std::thread::sleep(Duration::from_millis(2));
+        assert!(clock.now() > first);
+    }
+}
@@ -0,0 +1,385 @@
+//! Wrapper types for interpolating colors in perceptual color spaces.
+
+use gpui::{Background, Fill, Hsla, Rgba};
+
+use crate::{Components, Lerp};
+
+/// A wrapper around [`Rgba`] that interpolates in linear sRGB.
+///
+/// Blending in linear light avoids the dark, muddy midpoints you get when
+/// interpolating the gamma-encoded channels of an [`Rgba`] directly, which makes
+/// it a good fit for crossfades between bright colors.
+///
+/// # Example
+///
+/// ```ignore
+/// use gpui_transitions::LinearRgba;
+///
+/// let color = window.use_transition(cx, Duration::from_millis(300), |_window, _cx| {
+///     LinearRgba(rgb(0xFF0000))
+/// });
+///
+/// div().bg(color.evaluate(window, cx))
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LinearRgba(pub Rgba);
+
+/// A wrapper around [`Rgba`] that interpolates in the
+/// [Oklab](https://bottosson.github.io/posts/oklab/) color space.
+///
+/// Oklab is perceptually uniform, so transitions change lightness and hue at an
+/// even pace and stay vivid through the middle. This is usually the best choice
+/// for animating between two unrelated colors.
+///
+/// # Example
+///
+/// ```ignore
+/// use gpui_transitions::Oklab;
+///
+/// let color = window.use_transition(cx, Duration::from_millis(300), |_window, _cx| {
+///     Oklab(rgb(0xFF0000))
+/// });
+///
+/// div().bg(color.evaluate(window, cx))
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Oklab(pub Rgba);
+
+/// A wrapper around [`Rgba`] that interpolates in Oklch, the polar form of
+/// [`Oklab`].
+///
+/// Lightness, chroma and hue are interpolated separately, with the hue taking
+/// the shortest way around the color wheel. This keeps colors saturated through
+/// the whole transition, at the cost of passing through intermediate hues.
+///
+/// # Example
+///
+/// ```ignore
+/// use gpui_transitions::Oklch;
+///
+/// let color = window.use_transition(cx, Duration::from_millis(300), |_window, _cx| {
+///     Oklch(rgb(0xFF0000))
+/// });
+///
+/// div().bg(color.evaluate(window, cx))
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Oklch(pub Rgba);
+
+macro_rules! color_wrappers {
+    ( $( $ty:ident ),+ ) => {
+        $(
+            impl From<Rgba> for $ty {
+                fn from(value: Rgba) -> Self {
+                    $ty(value)
+                }
+            }
+
+            impl From<Hsla> for $ty {
+                fn from(value: Hsla) -> Self {
+                    $ty(value.into())
+                }
+            }
+
+            impl From<$ty> for Rgba {
+                fn from(value: $ty) -> Self {
+                    value.0
+                }
+            }
+
+            impl From<$ty> for Hsla {
+                fn from(value: $ty) -> Self {
+                    value.0.into()
+                }
+            }
+
+            impl From<$ty> for Fill {
+                fn from(value: $ty) -> Self {
+                    value.0.into()
+                }
+            }
+
+            impl From<$ty> for Background {
+                fn from(value: $ty) -> Self {
+                    Hsla::from(value.0).into()
+                }
+            }
+        )+
+    };
+}
+
+color_wrappers!(LinearRgba, Oklab, Oklch);
+
+impl Lerp for LinearRgba {
+    fn lerp(&self, to: &Self, delta: f32) -> Self {
+        let from = to_linear(self.0);
+        let to = to_linear(to.0);
+
+        LinearRgba(from_linear([
+            from[0].lerp(&to[0], delta),
+            from[1].lerp(&to[1], delta),
+            from[2].lerp(&to[2], delta),
+            from[3].lerp(&to[3], delta),
+        ]))
+    }
+}
+
+impl Lerp for Oklab {
+    fn lerp(&self, to: &Self, delta: f32) -> Self {
+        let from = to_oklab(self.0);
+        let to = to_oklab(to.0);
+
+        Oklab(from_oklab([
+            from[0].lerp(&to[0], delta),
+            from[1].lerp(&to[1], delta),
+            from[2].lerp(&to[2], delta),
+            from[3].lerp(&to[3], delta),
+        ]))
+    }
+}
+
+impl Lerp for Oklch {
+    fn lerp(&self, to: &Self, delta: f32) -> Self {
+        let [from_l, from_c, from_h, from_a] = to_oklch(self.0);
+        let [to_l, to_c, to_h, to_a] = to_oklch(to.0);
+
+        // A color without chroma (i.e. a grey) has no meaningful hue, so it takes
+        // the hue of the other color instead of swinging through unrelated ones.
+        let (from_h, to_h) = match (from_c < ACHROMATIC_CHROMA, to_c < ACHROMATIC_CHROMA) {
+            (true, false) => (to_h, to_h),
+            (false, true) => (from_h, from_h),
+            _ => (from_h, to_h),
+        };
+
+        Oklch(from_oklch([
+            from_l.lerp(&to_l, delta),
+            // Chroma can't be negative, which an overshooting delta could make it.
+            from_c.lerp(&to_c, delta).max(0.),
+            lerp_hue(from_h, to_h, delta),
+            from_a.lerp(&to_a, delta),
+        ]))
+    }
+}
+
+impl Components for LinearRgba {
+    fn write_components(&self, out: &mut Vec<f32>) {
+        out.extend(to_linear(self.0));
+    }
+
+    fn read_components(components: &mut impl Iterator<Item = f32>) -> Self {
+        LinearRgba(from_linear(read_four(components)))
+    }
+}
+
+impl Components for Oklab {
+    fn write_components(&self, out: &mut Vec<f32>) {
+        out.extend(to_oklab(self.0));
+    }
+
+    fn read_components(components: &mut impl Iterator<Item = f32>) -> Self {
+        Oklab(from_oklab(read_four(components)))
+    }
+}
+
+/// Below this chroma an Oklch color is treated as grey.
+const ACHROMATIC_CHROMA: f32 = 1e-4;
+
+/// Interpolates between two hues in the range 0 to 1, taking the shortest way
+/// around the color wheel.
+pub(crate) fn lerp_hue(from: f32, to: f32, delta: f32) -> f32 {
+    let mut diff = (to - from).rem_euclid(1.);
+    if diff > 0.5 {
+        diff -= 1.;
+    }
+
+    (from + diff * delta).rem_euclid(1.)
+}
+
+fn read_four(components: &mut impl Iterator<Item = f32>) -> [f32; 4] {
+    std::array::from_fn(|_| components.next().unwrap_or_default())
+}
+
+fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(c: f32) -> f32 {
+    if c <= 0.0031308 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1. / 2.4) - 0.055
+    }
+}
+
+/// Converts to linear sRGB, as `[r, g, b, a]`.
+fn to_linear(color: Rgba) -> [f32; 4] {
+    [
+        srgb_to_linear(color.r),
+        srgb_to_linear(color.g),
+        srgb_to_linear(color.b),
+        color.a,
+    ]
+}
+
+/// Converts from linear sRGB, clamping colors that fall outside of the sRGB gamut.
+fn from_linear([r, g, b, a]: [f32; 4]) -> Rgba {
+    Rgba {
+        r: linear_to_srgb(r).clamp(0., 1.),
+        g: linear_to_srgb(g).clamp(0., 1.),
+        b: linear_to_srgb(b).clamp(0., 1.),
+        a: a.clamp(0., 1.),
+    }
+}
+
+/// Converts to Oklab, as `[l, a, b, alpha]`.
+#[allow(clippy::excessive_precision)]
+fn to_oklab(color: Rgba) -> [f32; 4] {
+    let [r, g, b, alpha] = to_linear(color);
+
+    let l = (0.41222147 * r + 0.53633254 * g + 0.05144599 * b).cbrt();
+    let m = (0.21190350 * r + 0.68069954 * g + 0.10739696 * b).cbrt();
+    let s = (0.08830246 * r + 0.28171884 * g + 0.62997870 * b).cbrt();
+
+    [
+        0.21045426 * l + 0.79361779 * m - 0.00407205 * s,
+        1.97799850 * l - 2.42859221 * m + 0.45059371 * s,
+        0.02590404 * l + 0.78277177 * m - 0.80867577 * s,
+        alpha,
+    ]
+}
+
+#[allow(clippy::excessive_precision)]
+fn from_oklab([l, a, b, alpha]: [f32; 4]) -> Rgba {
+    let l_ = l + 0.39633778 * a + 0.21580376 * b;
+    let m_ = l - 0.10556135 * a - 0.06385417 * b;
+    let s_ = l - 0.08948418 * a - 1.29148555 * b;
+
+    let (l, m, s) = (l_.powi(3), m_.powi(3), s_.powi(3));
+
+    from_linear([
+        4.07674166 * l - 3.30771159 * m + 0.23096993 * s,
+        -1.26843800 * l + 2.60975740 * m - 0.34131940 * s,
+        -0.00419609 * l - 0.70341861 * m + 1.70761470 * s,
+        alpha,
+    ])
+}
+
+/// Converts to Oklch, as `[l, c, h, alpha]` with the hue in the range 0 to 1.
+fn to_oklch(color: Rgba) -> [f32; 4] {
+    let [l, a, b, alpha] = to_oklab(color);
+    let hue = b.atan2(a) / std::f32::consts::TAU;
+
+    [l, a.hypot(b), hue.rem_euclid(1.), alpha]
+}
+
+fn from_oklch([l, c, h, alpha]: [f32; 4]) -> Rgba {
+    let (sin, cos) = (h * std::f32::consts::TAU).sin_cos();
+    from_oklab([l, c * cos, c * sin, alpha])
+}
+
+#[cfg(all(test, feature = "test-support"))]
+mod tests {
+    use super::*;
+
+    const RED: Rgba = Rgba {
+        r: 1.0,
+        g: 0.0,
+        b: 0.0,
+        a: 1.0,
+    };
+
+    const BLUE: Rgba = Rgba {
+        r: 0.0,
+        g: 0.0,
+        b: 1.0,
+        a: 1.0,
+    };
+
+    const WHITE: Rgba = Rgba {
+        r: 1.0,
+        g: 1.0,
+        b: 1.0,
+        a: 1.0,
+    };
+
+    fn assert_rgba_near(a: Rgba, b: Rgba) {
+        let close = (a.r - b.r).abs() < 0.002
+            && (a.g - b.g).abs() < 0.002
+            && (a.b - b.b).abs() < 0.002
+            && (a.a - b.a).abs() < 0.002;
+        assert!(close, "{a:?} is not close to {b:?}");
+    }
+
+    #[test]
+    fn test_round_trips() {
+        let color = Rgba {
+            r: 0.2,
+            g: 0.6,
+            b: 0.9,
+            a: 0.5,
+        };
+
+        assert_rgba_near(from_linear(to_linear(color)), color);
+        assert_rgba_near(from_oklab(to_oklab(color)), color);
+        assert_rgba_near(from_oklch(to_oklch(color)), color);
+    }
+
+    #[test]
+    fn test_endpoints_are_preserved() {
+        for delta in [0.0, 1.0] {
+            let expected = if delta == 0.0 { RED } else { BLUE };
+
+            assert_rgba_near(LinearRgba(RED).lerp(&LinearRgba(BLUE), delta).0, expected);
+            assert_rgba_near(Oklab(RED).lerp(&Oklab(BLUE), delta).0, expected);
+            assert_rgba_near(Oklch(RED).lerp(&Oklch(BLUE), delta).0, expected);
+        }
+    }
+
+    #[test]
+    fn test_linear_rgba_midpoint_is_brighter() {
+        let srgb = RED.lerp(&BLUE, 0.5);
+        let linear = LinearRgba(RED).lerp(&LinearRgba(BLUE), 0.5).0;
+
+        assert!(linear.r > srgb.r);
+        assert!(linear.b > srgb.b);
+    }
+
+    #[test]
+    fn test_oklab_midpoint_is_perceptually_even() {
+        let mid = Oklab(WHITE).lerp(&Oklab(BLUE), 0.5).0;
+        let [lightness, ..] = to_oklab(mid);
+
+        assert!(mid.b > mid.r && mid.b > mid.g);
+        assert!((lightness - (to_oklab(WHITE)[0] + to_oklab(BLUE)[0]) / 2.).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_oklch_grey_takes_other_hue() {
+        let mid = Oklch(WHITE).lerp(&Oklch(RED), 0.5).0;
+
+        assert!(mid.r > mid.g && mid.r > mid.b);
+    }
+
+    #[test]
+    fn test_lerp_hue_takes_shortest_path() {
+        assert!((lerp_hue(0.8, 0.0, 0.5) - 0.9).abs() < 1e-6);
+        assert!((lerp_hue(0.1, 0.9, 0.25) - 0.05).abs() < 1e-6);
+        assert!((lerp_hue(0.2, 0.4, 0.5) - 0.3).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_oklab_components_round_trip() {
+        let color = Oklab(Rgba {
+            r: 0.3,
+            g: 0.5,
+            b: 0.7,
+            a: 1.0,
+        });
+
+        assert_rgba_near(Oklab::from_components(&color.to_components()).0, color.0);
+    }
+}
@@ -0,0 +1,237 @@
+//! A trait for breaking values down into their numeric components.
+
+use std::fmt::Debug;
+
+use gpui::{
+    Bounds, Corners, DevicePixels, Edges, Percentage, Pixels, Point, Radians, Rems, Rgba, Size,
+    colors::Colors, px,
+};
+
+use crate::BoolLerp;
+
+/// A trait for types that can be flattened into, and rebuilt from, a list of
+/// `f32` components.
+///
+/// Where [`Lerp`](crate::Lerp) only knows how to blend two values, `Components`
+/// exposes the individual numbers a value is made of. This is what allows
+/// physics-based animations such as [`SpringTransition`](crate::SpringTransition)
+/// to track a position and velocity for every component of a value.
+///
+/// # Implementations
+///
+/// This trait is implemented for the same types as [`Lerp`](crate::Lerp):
+/// - Floating-point types: `f32`, `f64`
+/// - Integer types: `u8`, `u16`, `u32`, `u64`, `u128`, `usize`, `i8`, `i16`, `i32`, `i64`, `i128`, `isize`
+/// - GPUI geometry types: [`Point`], [`Size`], [`Edges`], [`Corners`], [`Bounds`]
+/// - GPUI color types: [`Rgba`], [`Colors`]
+/// - GPUI unit types: [`Pixels`], [`Rems`], [`DevicePixels`], [`Percentage`], [`Radians`]
+/// - [`BoolLerp`] for animating boolean-like values
+/// - [`LinearRgba`](crate::LinearRgba) and [`Oklab`](crate::Oklab), whose
+///   components are in their own color space
+///
+/// # Example
+///
+/// ```ignore
+/// use gpui_transitions::Components;
+///
+/// let point = Point { x: 1.0_f32, y: 2.0 };
+///
+/// let mut components = Vec::new();
+/// point.write_components(&mut components);
+/// assert_eq!(components, vec![1.0, 2.0]);
+///
+/// let rebuilt = Point::<f32>::read_components(&mut components.into_iter());
+/// assert_eq!(rebuilt, point);
+/// ```
+pub trait Components: Sized {
+    /// Appends the components of `self` to `out`.
+    fn write_components(&self, out: &mut Vec<f32>);
+
+    /// Builds a value by consuming its components from `components`, in the same
+    /// order they were written by [`write_components`](Self::write_components).
+    ///
+    /// Missing components are treated as zero.
+    fn read_components(components: &mut impl Iterator<Item = f32>) -> Self;
+
+    /// Returns the components of `self` as a new vector.
+    fn to_components(&self) -> Vec<f32> {
+        let mut out = Vec::new();
+        self.write_components(&mut out);
+        out
+    }
+
+    /// Builds a value from a slice of components.
+    fn from_components(components: &[f32]) -> Self {
+        Self::read_components(&mut components.iter().copied())
+    }
+}
+
+macro_rules! number_components {
+    ( $( $ty:ty ),+ ) => {
+        $(
+            impl Components for $ty {
+                fn write_components(&self, out: &mut Vec<f32>) {
+                    out.push(*self as f32);
+                }
+
+                fn read_components(components: &mut impl Iterator<Item = f32>) -> Self {
+                    components.next().unwrap_or_default() as $ty
+                }
+            }
+        )+
+    };
+}
+
+number_components!(
+    f32, f64, usize, u8, u16, u32, u64, u128, isize, i8, i16, i32, i64, i128
+);
+
+macro_rules! struct_components {
+    ( $( $ty:ident $( < $gen:ident > )? { $( $n:ident ),+ } ),+ $(,)? ) => {
+        $(
+            impl$(<$gen: Components + Clone + Debug + Default + PartialEq>)? Components for $ty$(<$gen>)? {
+                fn write_components(&self, out: &mut Vec<f32>) {
+                    $(
+                        self.$n.write_components(out);
+                    )+
+                }
+
+                fn read_components(components: &mut impl Iterator<Item = f32>) -> Self {
+                    $ty$(::<$gen>)? {
+                        $(
+                            $n: Components::read_components(components)
+                        ),+
+                    }
+                }
+            }
+        )+
+    };
+}
+
+struct_components!(
+    Point<T> { x, y },
+    Size<T> { width, height },
+    Edges<T> { top, right, bottom, left },
+    Corners<T> { top_left, top_right, bottom_right, bottom_left },
+    Bounds<T> { origin, size },
+    Rgba { r, g, b, a },
+    Colors { text, selected_text, background, disabled, selected, border, separator, container }
+);
+
+macro_rules! tuple_struct_components {
+    ( $( $ty:ident ( $n:ty ) ),+ ) => {
+        $(
+            impl Components for $ty {
+                fn write_components(&self, out: &mut Vec<f32>) {
+                    self.0.write_components(out);
+                }
+
+                fn read_components(components: &mut impl Iterator<Item = f32>) -> Self {
+                    $ty(<$n>::read_components(components))
+                }
+            }
+        )+
+    };
+}
+
+tuple_struct_components!(Radians(f32), Percentage(f32), DevicePixels(i32), Rems(f32));
+
+impl Components for Pixels {
+    fn write_components(&self, out: &mut Vec<f32>) {
+        out.push(self.to_f64() as f32);
+    }
+
+    fn read_components(components: &mut impl Iterator<Item = f32>) -> Self {
+        px(components.next().unwrap_or_default())
+    }
+}
+
+impl<N: Components + Copy + PartialOrd + From<u8>> Components for BoolLerp<N> {
+    fn write_components(&self, out: &mut Vec<f32>) {
+        self.value().write_components(out);
+    }
+
+    /// Values outside of 0 to 1 (e.g. from a spring overshooting its goal) are
+    /// clamped, since a `BoolLerp` can't represent them.
+    fn read_components(components: &mut impl Iterator<Item = f32>) -> Self {
+        let value = N::read_components(components);
+
+        if value < N::from(0) {
+            BoolLerp::falsey()
+        } else if value > N::from(1) {
+            BoolLerp::truthy()
+        } else {
+            BoolLerp::new(value)
+        }
+    }
+}
+
+#[cfg(all(test, feature = "test-support"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_f32_components() {
+        assert_eq!(4.5_f32.to_components(), vec![4.5]);
+        assert_eq!(f32::from_components(&[4.5]), 4.5);
+    }
+
+    #[test]
+    fn test_integer_components() {
+        assert_eq!(42_i32.to_components(), vec![42.0]);
+        assert_eq!(i32::from_components(&[42.0]), 42);
+        assert_eq!(u8::from_components(&[300.0]), 255);
+    }
+
+    #[test]
+    fn test_missing_components_are_zero() {
+        let point = Point::<f32>::from_components(&[3.0]);
+        assert_eq!(point, Point { x: 3.0, y: 0.0 });
+    }
+
+    #[test]
+    fn test_point_components_round_trip() {
+        let point: Point<f32> = Point { x: 10.0, y: -20.0 };
+        let components = point.to_components();
+
+        assert_eq!(components, vec![10.0, -20.0]);
+        assert_eq!(Point::<f32>::from_components(&components), point);
+    }
+
+    #[test]
+    fn test_bounds_components_round_trip() {
+        let bounds: Bounds<Pixels> = Bounds {
+            origin: Point {
+                x: px(1.0),
+                y: px(2.0),
+            },
+            size: Size {
+                width: px(3.0),
+                height: px(4.0),
+            },
+        };
+        let components = bounds.to_components();
+
+        assert_eq!(components, vec![1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(Bounds::<Pixels>::from_components(&components), bounds);
+    }
+
+    #[test]
+    fn test_rgba_components_round_trip() {
+        let rgba = Rgba {
+            r: 0.1,
+            g: 0.2,
+            b: 0.3,
+            a: 0.4,
+        };
+
+        assert_eq!(Rgba::from_components(&rgba.to_components()), rgba);
+    }
+
+    #[test]
+    fn test_bool_lerp_components_clamp() {
+        assert_eq!(BoolLerp::<f32>::from_components(&[1.3]).value(), 1.0);
+        assert_eq!(BoolLerp::<f32>::from_components(&[-0.2]).value(), 0.0);
+        assert_eq!(BoolLerp::<f32>::from_components(&[0.4]).value(), 0.4);
+    }
+}
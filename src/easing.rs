@@ -0,0 +1,997 @@
+//! Easing functions, including a parser for CSS easing strings.
+//!
+//! Every easing function takes a linear progress between 0 and 1 and returns
+//! the eased progress, and can be passed to
+//! [`Transition::with_easing`](crate::Transition::with_easing).
+//!
+//! This module provides the standard set of Penner easing functions, such as
+//! [`ease_out_cubic`] and [`ease_in_out_back`], along with combinators like
+//! [`reverse`] and [`chain`] that build new easing functions out of existing ones.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use gpui_transitions::easing;
+//!
+//! let transition = window
+//!     .use_transition(cx, Duration::from_millis(300), |_, _| 0.0_f32)
+//!     .with_easing(easing::parse("cubic-bezier(0.2, 0, 0, 1)")?);
+//!
+//! // An ease-out that settles with a bounce, without overshooting.
+//! let settle = easing::chain(easing::ease_out_cubic, easing::clamp(easing::ease_out_back), 0.6);
+//! ```
+
+use std::{
+    error::Error,
+    f32::consts::{PI, TAU},
+    fmt,
+};
+
+/// A linear easing function, which leaves the progress unchanged.
+pub fn linear(t: f32) -> f32 {
+    t
+}
+
+/// Eases in along a quarter sine wave.
+pub fn ease_in_sine(t: f32) -> f32 {
+    1. - (t * PI / 2.).cos()
+}
+
+/// Eases out along a quarter sine wave.
+pub fn ease_out_sine(t: f32) -> f32 {
+    (t * PI / 2.).sin()
+}
+
+/// Eases in and out along a half sine wave.
+pub fn ease_in_out_sine(t: f32) -> f32 {
+    -((t * PI).cos() - 1.) / 2.
+}
+
+/// Eases in with a quadratic curve.
+pub fn ease_in_quad(t: f32) -> f32 {
+    t * t
+}
+
+/// Eases out with a quadratic curve.
+pub fn ease_out_quad(t: f32) -> f32 {
+    1. - (1. - t) * (1. - t)
+}
+
+/// Eases in and out with a quadratic curve.
+pub fn ease_in_out_quad(t: f32) -> f32 {
+    if t < 0.5 {
+        2. * t * t
+    } else {
+        1. - (-2. * t + 2.).powi(2) / 2.
+    }
+}
+
+/// Eases in with a cubic curve.
+pub fn ease_in_cubic(t: f32) -> f32 {
+    t * t * t
+}
+
+/// Eases out with a cubic curve.
+pub fn ease_out_cubic(t: f32) -> f32 {
+    1. - (1. - t).powi(3)
+}
+
+/// Eases in and out with a cubic curve.
+pub fn ease_in_out_cubic(t: f32) -> f32 {
+    if t < 0.5 {
+        4. * t * t * t
+    } else {
+        1. - (-2. * t + 2.).powi(3) / 2.
+    }
+}
+
+/// Eases in with a quartic curve.
+pub fn ease_in_quart(t: f32) -> f32 {
+    t.powi(4)
+}
+
+/// Eases out with a quartic curve.
+pub fn ease_out_quart(t: f32) -> f32 {
+    1. - (1. - t).powi(4)
+}
+
+/// Eases in and out with a quartic curve.
+pub fn ease_in_out_quart(t: f32) -> f32 {
+    if t < 0.5 {
+        8. * t.powi(4)
+    } else {
+        1. - (-2. * t + 2.).powi(4) / 2.
+    }
+}
+
+/// Eases in with a quintic curve.
+pub fn ease_in_quint(t: f32) -> f32 {
+    t.powi(5)
+}
+
+/// Eases out with a quintic curve.
+pub fn ease_out_quint(t: f32) -> f32 {
+    1. - (1. - t).powi(5)
+}
+
+/// Eases in and out with a quintic curve.
+pub fn ease_in_out_quint(t: f32) -> f32 {
+    if t < 0.5 {
+        16. * t.powi(5)
+    } else {
+        1. - (-2. * t + 2.).powi(5) / 2.
+    }
+}
+
+/// Eases in with an exponential curve.
+pub fn ease_in_expo(t: f32) -> f32 {
+    if t <= 0. {
+        0.
+    } else {
+        2_f32.powf(10. * t - 10.)
+    }
+}
+
+/// Eases out with an exponential curve.
+pub fn ease_out_expo(t: f32) -> f32 {
+    if t >= 1. {
+        1.
+    } else {
+        1. - 2_f32.powf(-10. * t)
+    }
+}
+
+/// Eases in and out with an exponential curve.
+pub fn ease_in_out_expo(t: f32) -> f32 {
+    if t <= 0. {
+        0.
+    } else if t >= 1. {
+        1.
+    } else if t < 0.5 {
+        2_f32.powf(20. * t - 10.) / 2.
+    } else {
+        (2. - 2_f32.powf(-20. * t + 10.)) / 2.
+    }
+}
+
+/// Eases in along a quarter circle.
+pub fn ease_in_circ(t: f32) -> f32 {
+    1. - (1. - t * t).max(0.).sqrt()
+}
+
+/// Eases out along a quarter circle.
+pub fn ease_out_circ(t: f32) -> f32 {
+    (1. - (t - 1.).powi(2)).max(0.).sqrt()
+}
+
+/// Eases in and out along two quarter circles.
+pub fn ease_in_out_circ(t: f32) -> f32 {
+    if t < 0.5 {
+        (1. - (1. - (2. * t).powi(2)).max(0.).sqrt()) / 2.
+    } else {
+        ((1. - (-2. * t + 2.).powi(2)).max(0.).sqrt() + 1.) / 2.
+    }
+}
+
+const BACK_OVERSHOOT: f32 = 1.70158;
+
+/// Eases in after pulling back below 0.
+pub fn ease_in_back(t: f32) -> f32 {
+    (BACK_OVERSHOOT + 1.) * t * t * t - BACK_OVERSHOOT * t * t
+}
+
+/// Eases out after overshooting past 1.
+pub fn ease_out_back(t: f32) -> f32 {
+    let t = t - 1.;
+    1. + (BACK_OVERSHOOT + 1.) * t * t * t + BACK_OVERSHOOT * t * t
+}
+
+/// Eases in and out, pulling back below 0 and overshooting past 1.
+pub fn ease_in_out_back(t: f32) -> f32 {
+    let overshoot = BACK_OVERSHOOT * 1.525;
+
+    if t < 0.5 {
+        (2. * t).powi(2) * ((overshoot + 1.) * 2. * t - overshoot) / 2.
+    } else {
+        ((2. * t - 2.).powi(2) * ((overshoot + 1.) * (t * 2. - 2.) + overshoot) + 2.) / 2.
+    }
+}
+
+/// Eases in with a growing oscillation, like a stretched elastic band.
+pub fn ease_in_elastic(t: f32) -> f32 {
+    if t <= 0. {
+        0.
+    } else if t >= 1. {
+        1.
+    } else {
+        -(2_f32.powf(10. * t - 10.)) * ((t * 10. - 10.75) * TAU / 3.).sin()
+    }
+}
+
+/// Eases out with a decaying oscillation around 1, like a released elastic band.
+pub fn ease_out_elastic(t: f32) -> f32 {
+    if t <= 0. {
+        0.
+    } else if t >= 1. {
+        1.
+    } else {
+        2_f32.powf(-10. * t) * ((t * 10. - 0.75) * TAU / 3.).sin() + 1.
+    }
+}
+
+/// Eases in and out with an oscillation at either end.
+pub fn ease_in_out_elastic(t: f32) -> f32 {
+    let frequency = TAU / 4.5;
+
+    if t <= 0. {
+        0.
+    } else if t >= 1. {
+        1.
+    } else if t < 0.5 {
+        -(2_f32.powf(20. * t - 10.) * ((20. * t - 11.125) * frequency).sin()) / 2.
+    } else {
+        2_f32.powf(-20. * t + 10.) * ((20. * t - 11.125) * frequency).sin() / 2. + 1.
+    }
+}
+
+/// Eases in with bounces that grow towards the end.
+pub fn ease_in_bounce(t: f32) -> f32 {
+    1. - ease_out_bounce(1. - t)
+}
+
+/// Eases out with bounces that settle at 1, like a dropped ball.
+pub fn ease_out_bounce(t: f32) -> f32 {
+    const N: f32 = 7.5625;
+    const D: f32 = 2.75;
+
+    if t < 1. / D {
+        N * t * t
+    } else if t < 2. / D {
+        let t = t - 1.5 / D;
+        N * t * t + 0.75
+    } else if t < 2.5 / D {
+        let t = t - 2.25 / D;
+        N * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / D;
+        N * t * t + 0.984375
+    }
+}
+
+/// Eases in and out with bounces at either end.
+pub fn ease_in_out_bounce(t: f32) -> f32 {
+    if t < 0.5 {
+        (1. - ease_out_bounce(1. - 2. * t)) / 2.
+    } else {
+        (1. + ease_out_bounce(2. * t - 1.)) / 2.
+    }
+}
+
+/// Reverses an easing function, so that it starts the way it ended and ends the
+/// way it started. This turns an ease-in into the matching ease-out.
+pub fn reverse(easing: impl Fn(f32) -> f32) -> impl Fn(f32) -> f32 {
+    move |t| 1. - easing(1. - t)
+}
+
+/// Plays an easing function over the first half of the progress, and its
+/// [`reverse`] over the second half. This turns an ease-in into the matching
+/// ease-in-out.
+pub fn mirror(easing: impl Fn(f32) -> f32) -> impl Fn(f32) -> f32 {
+    move |t| {
+        if t < 0.5 {
+            easing(2. * t) / 2.
+        } else {
+            1. - easing(2. - 2. * t) / 2.
+        }
+    }
+}
+
+/// Plays `first` and then `second`, switching at `split`.
+///
+/// Each easing function covers its share of the progress and of the output, so
+/// `first` eases from 0 to `split` and `second` eases from `split` to 1.
+pub fn chain(
+    first: impl Fn(f32) -> f32,
+    second: impl Fn(f32) -> f32,
+    split: f32,
+) -> impl Fn(f32) -> f32 {
+    let split = split.clamp(0., 1.);
+
+    move |t| {
+        if t < split {
+            first(t / split) * split
+        } else if split >= 1. {
+            1.
+        } else {
+            split + second((t - split) / (1. - split)) * (1. - split)
+        }
+    }
+}
+
+/// Clamps the output of an easing function between 0 and 1, removing any
+/// overshoot.
+pub fn clamp(easing: impl Fn(f32) -> f32) -> impl Fn(f32) -> f32 {
+    move |t| easing(t).clamp(0., 1.)
+}
+
+/// Scales how far an easing function deviates from a linear one by `factor`.
+///
+/// A factor of 0 gives a linear easing function and a factor of 1 leaves it
+/// unchanged, while factors in between soften it and factors above 1 exaggerate
+/// it. The start and end points are kept.
+pub fn scale(easing: impl Fn(f32) -> f32, factor: f32) -> impl Fn(f32) -> f32 {
+    move |t| t + (easing(t) - t) * factor
+}
+
+/// Returns a cubic Bézier easing function, like CSS's `cubic-bezier()`.
+///
+/// The curve starts at (0, 0) and ends at (1, 1), with the given control points.
+/// `x1` and `x2` should be between 0 and 1, so that the curve is a function of
+/// its input. For each input, the curve's parameter is solved with Newton's
+/// method, falling back to bisection when it doesn't converge.
+pub fn cubic_bezier(x1: f32, y1: f32, x2: f32, y2: f32) -> impl Fn(f32) -> f32 + Clone {
+    let x = BezierAxis::new(x1, x2);
+    let y = BezierAxis::new(y1, y2);
+
+    move |progress| {
+        if progress <= 0. {
+            return 0.;
+        }
+        if progress >= 1. {
+            return 1.;
+        }
+
+        y.sample(x.solve(progress))
+    }
+}
+
+/// The polynomial coefficients of one axis of a cubic Bézier curve whose end
+/// points are 0 and 1.
+#[derive(Clone, Copy)]
+struct BezierAxis {
+    a: f32,
+    b: f32,
+    c: f32,
+}
+
+impl BezierAxis {
+    fn new(p1: f32, p2: f32) -> Self {
+        let c = 3. * p1;
+        let b = 3. * (p2 - p1) - c;
+        let a = 1. - c - b;
+
+        Self { a, b, c }
+    }
+
+    fn sample(&self, t: f32) -> f32 {
+        ((self.a * t + self.b) * t + self.c) * t
+    }
+
+    fn sample_derivative(&self, t: f32) -> f32 {
+        (3. * self.a * t + 2. * self.b) * t + self.c
+    }
+
+    /// Finds the parameter `t` at which this axis equals `value`.
+    fn solve(&self, value: f32) -> f32 {
+        const EPSILON: f32 = 1e-6;
+
+        let mut t = value;
+        for _ in 0..8 {
+            let error = self.sample(t) - value;
+            if error.abs() < EPSILON {
+                return t;
+            }
+
+            let derivative = self.sample_derivative(t);
+            if derivative.abs() < EPSILON {
+                break;
+            }
+
+            t -= error / derivative;
+        }
+
+        let (mut low, mut high) = (0., 1.);
+        t = value;
+
+        while high - low > EPSILON {
+            let sample = self.sample(t);
+            if (sample - value).abs() < EPSILON {
+                break;
+            }
+
+            if sample < value {
+                low = t;
+            } else {
+                high = t;
+            }
+            t = (low + high) / 2.;
+        }
+
+        t
+    }
+}
+
+/// Where the jumps of a [`steps`] easing function happen, like the second
+/// argument of CSS's `steps()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StepPosition {
+    /// The first jump happens at the start (`jump-start` or `start`).
+    JumpStart,
+
+    /// The last jump happens at the end (`jump-end` or `end`).
+    #[default]
+    JumpEnd,
+
+    /// There is no jump at either end (`jump-none`).
+    JumpNone,
+
+    /// There are jumps at both ends (`jump-both`).
+    JumpBoth,
+}
+
+/// Returns an easing function that moves in `count` equal steps, like CSS's
+/// `steps()`.
+///
+/// With [`StepPosition::JumpNone`], `count` must be at least 2.
+pub fn steps(count: u32, position: StepPosition) -> impl Fn(f32) -> f32 + Clone {
+    let count = count.max(1);
+
+    let jumps = match position {
+        StepPosition::JumpStart | StepPosition::JumpEnd => count,
+        StepPosition::JumpNone => count.saturating_sub(1).max(1),
+        StepPosition::JumpBoth => count + 1,
+    } as f32;
+
+    move |progress| {
+        let progress = progress.clamp(0., 1.);
+        let mut step = (progress * count as f32).floor();
+
+        if matches!(position, StepPosition::JumpStart | StepPosition::JumpBoth) {
+            step += 1.;
+        }
+
+        step.min(jumps) / jumps
+    }
+}
+
+/// A stop of a [`linear_stops`] easing function, like an argument of CSS's
+/// `linear()`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LinearStop {
+    /// The output of the easing function at this stop.
+    pub output: f32,
+
+    /// The input (between 0 and 1) at which the output is reached. When `None`,
+    /// the stop is placed halfway between its neighbours.
+    pub input: Option<f32>,
+}
+
+impl LinearStop {
+    /// Creates a stop at the given input.
+    pub fn new(output: f32, input: f32) -> Self {
+        Self {
+            output,
+            input: Some(input),
+        }
+    }
+
+    /// Creates a stop whose input is placed evenly between its neighbours.
+    pub fn auto(output: f32) -> Self {
+        Self {
+            output,
+            input: None,
+        }
+    }
+}
+
+/// Returns a piecewise linear easing function through the given stops, like
+/// CSS's `linear()`.
+///
+/// Missing inputs are resolved as they are in CSS: the first and last stops
+/// default to 0 and 1, inputs that are smaller than a preceding one are raised
+/// to it, and the remaining stops are spread evenly between their neighbours.
+/// Two stops at the same input produce an instant jump. With no stops, the
+/// function is linear.
+pub fn linear_stops(stops: &[LinearStop]) -> impl Fn(f32) -> f32 + Clone + use<> {
+    let points = resolve_linear_stops(stops);
+
+    move |progress| {
+        let progress = progress.clamp(0., 1.);
+
+        let Some(last) = points.last() else {
+            return progress;
+        };
+
+        // The last point at or before the progress.
+        let index = points.partition_point(|&(input, _)| input <= progress);
+        if index == 0 {
+            return points[0].1;
+        }
+        if index == points.len() {
+            return last.1;
+        }
+
+        let (start_input, start_output) = points[index - 1];
+        let (end_input, end_output) = points[index];
+        let t = (progress - start_input) / (end_input - start_input);
+
+        start_output + (end_output - start_output) * t
+    }
+}
+
+fn resolve_linear_stops(stops: &[LinearStop]) -> Vec<(f32, f32)> {
+    let mut inputs: Vec<Option<f32>> = stops.iter().map(|stop| stop.input).collect();
+
+    if let Some(first) = inputs.first_mut() {
+        first.get_or_insert(0.);
+    }
+    if let Some(last) = inputs.last_mut() {
+        last.get_or_insert(1.);
+    }
+
+    let mut max = f32::NEG_INFINITY;
+    for input in inputs.iter_mut().flatten() {
+        max = max.max(*input);
+        *input = max;
+    }
+
+    let mut index = 0;
+    while index < inputs.len() {
+        if inputs[index].is_some() {
+            index += 1;
+            continue;
+        }
+
+        // Spread the run of missing inputs evenly between its known neighbours.
+        let start = index - 1;
+        let end = (index..inputs.len())
+            .find(|&i| inputs[i].is_some())
+            .unwrap();
+        let (from, to) = (inputs[start].unwrap(), inputs[end].unwrap());
+
+        for (i, input) in inputs[index..end].iter_mut().enumerate() {
+            let t = (index + i - start) as f32 / (end - start) as f32;
+            *input = Some(from + (to - from) * t);
+        }
+        index = end;
+    }
+
+    inputs
+        .into_iter()
+        .zip(stops)
+        .map(|(input, stop)| (input.unwrap(), stop.output))
+        .collect()
+}
+
+/// An error returned by [`parse`] for strings that aren't valid CSS easing
+/// functions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseEasingError {
+    /// The string isn't a known keyword or function.
+    Unknown(String),
+
+    /// The arguments of a function are invalid.
+    InvalidArguments {
+        /// The name of the function.
+        function: &'static str,
+
+        /// Why the arguments are invalid.
+        reason: &'static str,
+    },
+}
+
+impl fmt::Display for ParseEasingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseEasingError::Unknown(input) => write!(f, "unknown easing function `{input}`"),
+            ParseEasingError::InvalidArguments { function, reason } => {
+                write!(f, "invalid arguments to `{function}()`: {reason}")
+            }
+        }
+    }
+}
+
+impl Error for ParseEasingError {}
+
+/// Parses a CSS easing function, such as `ease-in-out`,
+/// `cubic-bezier(0.2, 0, 0, 1)`, `steps(4, jump-end)` or `linear(0, 0.25 75%, 1)`.
+///
+/// Supported are the keywords `linear`, `ease`, `ease-in`, `ease-out`,
+/// `ease-in-out`, `step-start` and `step-end`, along with the `cubic-bezier()`,
+/// `steps()` and `linear()` functions from CSS Easing Level 2. Keywords and
+/// function names are case-insensitive.
+pub fn parse(input: &str) -> Result<Box<dyn Fn(f32) -> f32>, ParseEasingError> {
+    let trimmed = input.trim();
+    let lowercase = trimmed.to_ascii_lowercase();
+
+    let easing: Box<dyn Fn(f32) -> f32> = match lowercase.as_str() {
+        "linear" => Box::new(linear),
+        "ease" => Box::new(cubic_bezier(0.25, 0.1, 0.25, 1.)),
+        "ease-in" => Box::new(cubic_bezier(0.42, 0., 1., 1.)),
+        "ease-out" => Box::new(cubic_bezier(0., 0., 0.58, 1.)),
+        "ease-in-out" => Box::new(cubic_bezier(0.42, 0., 0.58, 1.)),
+        "step-start" => Box::new(steps(1, StepPosition::JumpStart)),
+        "step-end" => Box::new(steps(1, StepPosition::JumpEnd)),
+        _ => {
+            let Some((name, arguments)) = lowercase
+                .strip_suffix(')')
+                .and_then(|function| function.split_once('('))
+            else {
+                return Err(ParseEasingError::Unknown(trimmed.to_string()));
+            };
+
+            let arguments: Vec<&str> = arguments.split(',').map(str::trim).collect();
+
+            match name.trim() {
+                "cubic-bezier" => parse_cubic_bezier(&arguments)?,
+                "steps" => parse_steps(&arguments)?,
+                "linear" => parse_linear(&arguments)?,
+                _ => return Err(ParseEasingError::Unknown(trimmed.to_string())),
+            }
+        }
+    };
+
+    Ok(easing)
+}
+
+fn invalid(function: &'static str, reason: &'static str) -> ParseEasingError {
+    ParseEasingError::InvalidArguments { function, reason }
+}
+
+fn parse_number(function: &'static str, argument: &str) -> Result<f32, ParseEasingError> {
+    argument
+        .parse::<f32>()
+        .ok()
+        .filter(|number| number.is_finite())
+        .ok_or_else(|| invalid(function, "expected a number"))
+}
+
+fn parse_cubic_bezier(arguments: &[&str]) -> Result<Box<dyn Fn(f32) -> f32>, ParseEasingError> {
+    const NAME: &str = "cubic-bezier";
+
+    let [x1, y1, x2, y2] = arguments else {
+        return Err(invalid(NAME, "expected 4 numbers"));
+    };
+
+    let [x1, y1, x2, y2] = [x1, y1, x2, y2].map(|argument| parse_number(NAME, argument));
+    let (x1, y1, x2, y2) = (x1?, y1?, x2?, y2?);
+
+    if !(0. ..=1.).contains(&x1) || !(0. ..=1.).contains(&x2) {
+        return Err(invalid(NAME, "x coordinates must be between 0 and 1"));
+    }
+
+    Ok(Box::new(cubic_bezier(x1, y1, x2, y2)))
+}
+
+fn parse_steps(arguments: &[&str]) -> Result<Box<dyn Fn(f32) -> f32>, ParseEasingError> {
+    const NAME: &str = "steps";
+
+    let (count, position) = match arguments {
+        [count] => (count, StepPosition::default()),
+        [count, position] => {
+            let position = match *position {
+                "jump-start" | "start" => StepPosition::JumpStart,
+                "jump-end" | "end" => StepPosition::JumpEnd,
+                "jump-none" => StepPosition::JumpNone,
+                "jump-both" => StepPosition::JumpBoth,
+                _ => return Err(invalid(NAME, "unknown step position")),
+            };
+            (count, position)
+        }
+        _ => {
+            return Err(invalid(
+                NAME,
+                "expected a step count and an optional position",
+            ));
+        }
+    };
+
+    let count: u32 = count
+        .parse()
+        .map_err(|_| invalid(NAME, "expected a whole step count"))?;
+
+    let minimum = if position == StepPosition::JumpNone {
+        2
+    } else {
+        1
+    };
+    if count < minimum {
+        return Err(invalid(NAME, "too few steps"));
+    }
+
+    Ok(Box::new(steps(count, position)))
+}
+
+fn parse_linear(arguments: &[&str]) -> Result<Box<dyn Fn(f32) -> f32>, ParseEasingError> {
+    const NAME: &str = "linear";
+
+    let mut stops = Vec::new();
+
+    for argument in arguments {
+        let mut parts = argument.split_whitespace();
+
+        let output = parse_number(NAME, parts.next().unwrap_or_default())?;
+        let inputs: Vec<f32> = parts
+            .map(|part| {
+                part.strip_suffix('%')
+                    .ok_or_else(|| invalid(NAME, "expected a percentage"))
+                    .and_then(|percentage| parse_number(NAME, percentage))
+                    .map(|percentage| percentage / 100.)
+            })
+            .collect::<Result<_, _>>()?;
+
+        match inputs.as_slice() {
+            [] => stops.push(LinearStop::auto(output)),
+            [input] => stops.push(LinearStop::new(output, *input)),
+            // Two percentages are shorthand for two stops with the same output.
+            [start, end] => {
+                stops.push(LinearStop::new(output, *start));
+                stops.push(LinearStop::new(output, *end));
+            }
+            _ => return Err(invalid(NAME, "expected at most 2 percentages per stop")),
+        }
+    }
+
+    if stops.len() < 2 {
+        return Err(invalid(NAME, "expected at least 2 stops"));
+    }
+
+    Ok(Box::new(linear_stops(&stops)))
+}
+
+#[cfg(all(test, feature = "test-support"))]
+mod tests {
+    use super::*;
+
+    fn assert_near(actual: f32, expected: f32) {
+        assert!(
+            (actual - expected).abs() < 0.001,
+            "{actual} is not close to {expected}"
+        );
+    }
+
+    const PENNER: [fn(f32) -> f32; 30] = [
+        ease_in_sine,
+        ease_out_sine,
+        ease_in_out_sine,
+        ease_in_quad,
+        ease_out_quad,
+        ease_in_out_quad,
+        ease_in_cubic,
+        ease_out_cubic,
+        ease_in_out_cubic,
+        ease_in_quart,
+        ease_out_quart,
+        ease_in_out_quart,
+        ease_in_quint,
+        ease_out_quint,
+        ease_in_out_quint,
+        ease_in_expo,
+        ease_out_expo,
+        ease_in_out_expo,
+        ease_in_circ,
+        ease_out_circ,
+        ease_in_out_circ,
+        ease_in_back,
+        ease_out_back,
+        ease_in_out_back,
+        ease_in_elastic,
+        ease_out_elastic,
+        ease_in_out_elastic,
+        ease_in_bounce,
+        ease_out_bounce,
+        ease_in_out_bounce,
+    ];
+
+    #[test]
+    fn test_penner_endpoints() {
+        for (index, easing) in PENNER.iter().enumerate() {
+            assert!(
+                easing(0.).abs() < 0.001,
+                "easing {index} doesn't start at 0"
+            );
+            assert!(
+                (easing(1.) - 1.).abs() < 0.001,
+                "easing {index} doesn't end at 1"
+            );
+        }
+    }
+
+    #[test]
+    fn test_penner_in_out_pairs() {
+        // Each ease-out is the reverse of its ease-in, and each ease-in-out is its
+        // ease-in mirrored, except for back and elastic, whose in-out variants
+        // use their own overshoot and frequency.
+        for (index, pair) in PENNER.chunks(3).enumerate() {
+            let [ease_in, ease_out, ease_in_out] = pair else {
+                unreachable!()
+            };
+
+            for t in [0.1, 0.3, 0.5, 0.7, 0.9] {
+                assert_near(reverse(ease_in)(t), ease_out(t));
+                if ![7, 8].contains(&index) {
+                    assert_near(mirror(ease_in)(t), ease_in_out(t));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_back_overshoots() {
+        assert!(ease_in_back(0.2) < 0.);
+        assert!(ease_out_back(0.8) > 1.);
+    }
+
+    #[test]
+    fn test_chain() {
+        let easing = chain(ease_in_quad, ease_out_quad, 0.25);
+
+        assert_eq!(easing(0.), 0.);
+        assert_near(easing(0.125), 0.0625);
+        assert_near(easing(0.25), 0.25);
+        assert_near(easing(0.625), 0.25 + ease_out_quad(0.5) * 0.75);
+        assert_eq!(easing(1.), 1.);
+
+        assert_eq!(chain(ease_in_quad, ease_out_quad, 1.)(1.), 1.);
+        assert_eq!(chain(ease_in_quad, ease_out_quad, 0.)(0.5), 0.75);
+    }
+
+    #[test]
+    fn test_clamp_and_scale() {
+        assert_eq!(clamp(ease_out_back)(0.8), 1.);
+        assert_eq!(clamp(ease_in_back)(0.2), 0.);
+
+        assert_eq!(scale(ease_in_quad, 0.)(0.5), 0.5);
+        assert_eq!(scale(ease_in_quad, 1.)(0.5), 0.25);
+        assert_eq!(scale(ease_in_quad, 0.5)(0.5), 0.375);
+        assert_eq!(scale(ease_in_quad, 2.)(1.), 1.);
+    }
+
+    #[test]
+    fn test_cubic_bezier_endpoints() {
+        let ease = cubic_bezier(0.25, 0.1, 0.25, 1.);
+
+        assert_eq!(ease(0.), 0.);
+        assert_eq!(ease(1.), 1.);
+    }
+
+    #[test]
+    fn test_cubic_bezier_known_values() {
+        let ease = cubic_bezier(0.25, 0.1, 0.25, 1.);
+        assert_near(ease(0.25), 0.4085);
+        assert_near(ease(0.5), 0.8024);
+
+        // A linear curve.
+        let linear = cubic_bezier(1. / 3., 1. / 3., 2. / 3., 2. / 3.);
+        assert_near(linear(0.3), 0.3);
+    }
+
+    #[test]
+    fn test_cubic_bezier_flat_derivative_falls_back_to_bisection() {
+        // The x axis has a zero derivative at its midpoint.
+        let easing = cubic_bezier(1., 0., 0., 1.);
+
+        assert_near(easing(0.5), 0.5);
+        assert!(easing(0.4) < 0.5);
+        assert!(easing(0.6) > 0.5);
+    }
+
+    #[test]
+    fn test_steps() {
+        let end = steps(4, StepPosition::JumpEnd);
+        assert_eq!(end(0.), 0.);
+        assert_eq!(end(0.3), 0.25);
+        assert_eq!(end(0.99), 0.75);
+        assert_eq!(end(1.), 1.);
+
+        let start = steps(4, StepPosition::JumpStart);
+        assert_eq!(start(0.), 0.25);
+        assert_eq!(start(0.3), 0.5);
+        assert_eq!(start(1.), 1.);
+
+        let none = steps(3, StepPosition::JumpNone);
+        assert_eq!(none(0.), 0.);
+        assert_eq!(none(0.5), 0.5);
+        assert_eq!(none(1.), 1.);
+
+        let both = steps(3, StepPosition::JumpBoth);
+        assert_eq!(both(0.), 0.25);
+        assert_eq!(both(0.5), 0.5);
+        assert_eq!(both(1.), 1.);
+    }
+
+    #[test]
+    fn test_linear_stops() {
+        let easing = linear_stops(&[
+            LinearStop::auto(0.),
+            LinearStop::new(0.25, 0.75),
+            LinearStop::auto(1.),
+        ]);
+
+        assert_eq!(easing(0.), 0.);
+        assert_near(easing(0.375), 0.125);
+        assert_near(easing(0.75), 0.25);
+        assert_near(easing(0.875), 0.625);
+        assert_eq!(easing(1.), 1.);
+    }
+
+    #[test]
+    fn test_linear_stops_spread_missing_inputs() {
+        let easing = linear_stops(&[
+            LinearStop::auto(0.),
+            LinearStop::auto(0.5),
+            LinearStop::auto(0.6),
+            LinearStop::auto(1.),
+        ]);
+
+        assert_near(easing(1. / 3.), 0.5);
+        assert_near(easing(2. / 3.), 0.6);
+    }
+
+    #[test]
+    fn test_linear_stops_jump() {
+        let easing = linear_stops(&[
+            LinearStop::new(0., 0.),
+            LinearStop::new(0., 0.5),
+            LinearStop::new(1., 0.5),
+            LinearStop::new(1., 1.),
+        ]);
+
+        assert_eq!(easing(0.49), 0.);
+        assert_eq!(easing(0.5), 1.);
+    }
+
+    #[test]
+    fn test_parse_keywords() {
+        assert_eq!(parse("linear").unwrap()(0.3), 0.3);
+        assert_near(parse("ease").unwrap()(0.5), 0.8024);
+        assert_near(parse(" Ease-In-Out ").unwrap()(0.5), 0.5);
+        assert_eq!(parse("step-start").unwrap()(0.), 1.);
+        assert_eq!(parse("step-end").unwrap()(0.99), 0.);
+    }
+
+    #[test]
+    fn test_parse_functions() {
+        assert_near(
+            parse("cubic-bezier(0.25, 0.1, 0.25, 1)").unwrap()(0.5),
+            0.8024,
+        );
+        assert_eq!(parse("steps(4, jump-end)").unwrap()(0.3), 0.25);
+        assert_eq!(parse("steps(4)").unwrap()(0.3), 0.25);
+        assert_eq!(parse("steps(4, start)").unwrap()(0.3), 0.5);
+        assert_near(parse("linear(0, 0.25 75%, 1)").unwrap()(0.875), 0.625);
+        assert_eq!(parse("linear(0, 1 50% 100%)").unwrap()(0.75), 1.);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse("bounce").err(),
+            Some(ParseEasingError::Unknown("bounce".into()))
+        );
+        assert!(matches!(
+            parse("cubic-bezier(0.2, 0, 0)"),
+            Err(ParseEasingError::InvalidArguments { .. })
+        ));
+        assert!(matches!(
+            parse("cubic-bezier(1.5, 0, 0, 1)"),
+            Err(ParseEasingError::InvalidArguments { .. })
+        ));
+        assert!(matches!(
+            parse("steps(1, jump-none)"),
+            Err(ParseEasingError::InvalidArguments { .. })
+        ));
+        assert!(matches!(
+            parse("steps(0)"),
+            Err(ParseEasingError::InvalidArguments { .. })
+        ));
+        assert!(matches!(
+            parse("linear(0)"),
+            Err(ParseEasingError::InvalidArguments { .. })
+        ));
+        assert!(matches!(
+            parse("linear(0, 1 50)"),
+            Err(ParseEasingError::InvalidArguments { .. })
+        ));
+    }
+}
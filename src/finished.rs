@@ -0,0 +1,107 @@
+//! A future that resolves once a transition reaches its goal.
+
+use std::{
+    cell::RefCell,
+    future::Future,
+    pin::Pin,
+    rc::Rc,
+    task::{Context, Poll, Waker},
+};
+
+use gpui::{App, Entity, Subscription};
+
+/// How the goal a [`TransitionFinished`] future was waiting on ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionOutcome {
+    /// The transition reached the goal.
+    Completed,
+
+    /// The goal was replaced by a newer one, or the transition was reset, before
+    /// it was reached.
+    Superseded,
+}
+
+#[derive(Default)]
+struct Slot {
+    outcome: Option<TransitionOutcome>,
+    waker: Option<Waker>,
+}
+
+impl Slot {
+    fn resolve(&mut self, outcome: TransitionOutcome) {
+        if self.outcome.is_some() {
+            return;
+        }
+
+        self.outcome = Some(outcome);
+
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// A future that resolves once the goal of a transition, as it was when the
+/// future was created, is either reached or superseded.
+///
+/// Created by [`Transition::finished`](crate::Transition::finished). The future
+/// observes the transition's state entity, so it is woken whenever that entity
+/// is notified rather than polling on a timer. Dropping the future stops
+/// observing the state.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct TransitionFinished {
+    slot: Rc<RefCell<Slot>>,
+    _subscription: Option<Subscription>,
+}
+
+impl TransitionFinished {
+    /// Creates a future that checks `outcome` against the state every time the
+    /// state entity is notified, and resolves with the first outcome it returns.
+    pub(crate) fn new<S: 'static>(
+        state: &Entity<S>,
+        cx: &mut App,
+        outcome: impl Fn(&S, &App) -> Option<TransitionOutcome> + 'static,
+    ) -> Self {
+        let slot = Rc::new(RefCell::new(Slot::default()));
+
+        if let Some(outcome) = outcome(state.read(cx), cx) {
+            slot.borrow_mut().resolve(outcome);
+
+            return Self {
+                slot,
+                _subscription: None,
+            };
+        }
+
+        let subscription = cx.observe(state, {
+            let slot = slot.clone();
+
+            move |state, cx| {
+                if let Some(outcome) = outcome(state.read(cx), cx) {
+                    slot.borrow_mut().resolve(outcome);
+                }
+            }
+        });
+
+        Self {
+            slot,
+            _subscription: Some(subscription),
+        }
+    }
+}
+
+impl Future for TransitionFinished {
+    type Output = TransitionOutcome;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut slot = self.slot.borrow_mut();
+
+        match slot.outcome {
+            Some(outcome) => Poll::Ready(outcome),
+            None => {
+                slot.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
@@ -0,0 +1,692 @@
+//! Transitions whose progress is driven directly by a drag gesture.
+
+use std::{
+    borrow::BorrowMut,
+    cell::RefCell,
+    collections::VecDeque,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use gpui::{App, Entity, EntityId, Window};
+
+use crate::{
+    Clock, MotionSettings, SystemClock,
+    spring::{MAX_STEPS, STEP_SECS, settle_secs},
+};
+
+/// How far back drag samples are used to estimate the velocity.
+const VELOCITY_WINDOW: Duration = Duration::from_millis(100);
+
+/// The distance from the snap point and speed (in progress per second) below
+/// which a released spring comes to rest.
+const REST_THRESHOLD: f32 = 0.001;
+
+/// How a [`GestureTransition`] moves to its snap point once it is released.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GestureRelease {
+    /// A damped spring pulls the progress to the snap point, starting out with
+    /// the velocity of the drag.
+    Spring {
+        /// How strongly the spring pulls toward the snap point.
+        stiffness: f32,
+        /// How strongly the spring's motion is resisted.
+        damping: f32,
+        /// The mass attached to the spring.
+        mass: f32,
+    },
+
+    /// The progress reaches the snap point after the given duration. It starts
+    /// out with the velocity of the drag and eases out to a stop.
+    Duration(Duration),
+}
+
+impl Default for GestureRelease {
+    fn default() -> Self {
+        GestureRelease::Spring {
+            stiffness: 170.,
+            damping: 26.,
+            mass: 1.,
+        }
+    }
+}
+
+/// The motion of a released [`GestureTransition`] toward its snap point.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Settle {
+    pub(crate) target: f32,
+    pub(crate) from: f32,
+    pub(crate) from_velocity: f32,
+    /// How long (in seconds) the motion has played, at its playback rate.
+    pub(crate) elapsed_secs: f32,
+    pub(crate) last_stepped_at: Instant,
+}
+
+/// Internal state container for a [`GestureTransition`].
+///
+/// This struct holds the current progress and velocity, the recent drag samples
+/// used to estimate the velocity, and the motion toward a snap point once the
+/// drag has been released.
+///
+/// You typically don't need to interact with this type directly. It's created
+/// and managed by [`GestureTransition`] and the
+/// [`WindowUseTransition`](crate::WindowUseTransition) methods.
+#[derive(Clone)]
+pub struct GestureState {
+    pub(crate) initial_progress: f32,
+    pub(crate) progress: f32,
+    /// The velocity in progress per second.
+    pub(crate) velocity: f32,
+    pub(crate) dragging: bool,
+    /// Recent drag samples, oldest first.
+    pub(crate) samples: VecDeque<(Instant, f32)>,
+    pub(crate) settle: Option<Settle>,
+    pub(crate) clock: Rc<dyn Clock>,
+}
+
+impl GestureState {
+    /// Creates a new gesture state at rest on the given progress.
+    pub fn new(initial_progress: f32) -> Self {
+        Self {
+            initial_progress,
+            progress: initial_progress,
+            velocity: 0.,
+            dragging: false,
+            samples: VecDeque::new(),
+            settle: None,
+            clock: Rc::new(SystemClock),
+        }
+    }
+
+    /// Sets the clock used to read the current time.
+    ///
+    /// Defaults to [`SystemClock`].
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Rc::new(clock);
+        self
+    }
+
+    /// Estimates the drag velocity from the samples within the velocity window.
+    fn drag_velocity(&self, now: Instant) -> f32 {
+        let Some(&(newest_at, newest)) = self.samples.back() else {
+            return 0.;
+        };
+
+        // The pointer has been held still.
+        if now.saturating_duration_since(newest_at) > VELOCITY_WINDOW {
+            return 0.;
+        }
+
+        let Some(&(oldest_at, oldest)) = self
+            .samples
+            .iter()
+            .find(|(at, _)| newest_at.saturating_duration_since(*at) <= VELOCITY_WINDOW)
+        else {
+            return 0.;
+        };
+
+        let elapsed_secs = newest_at.saturating_duration_since(oldest_at).as_secs_f32();
+        if elapsed_secs > 0. {
+            (newest - oldest) / elapsed_secs
+        } else {
+            self.velocity
+        }
+    }
+}
+
+/// A transition whose progress follows a drag gesture, and which carries the
+/// drag's velocity over into its motion toward a snap point once released.
+///
+/// While dragging, [`drag`](Self::drag) sets the progress directly, typically from
+/// GPUI's `on_drag_move`, and the velocity is estimated from the most recent
+/// samples. [`release`](Self::release) then picks the snap point closest to where
+/// the velocity would carry the progress, and moves there with a
+/// [`GestureRelease`], either a spring or a fixed duration.
+///
+/// The progress is a plain `f32`, usually between 0 and 1, which can be mapped
+/// to any value with [`Lerp`](crate::Lerp).
+///
+/// # Example
+///
+/// ```ignore
+/// let drawer = window
+///     .use_keyed_gesture("drawer", cx, |_, _| 0.0)
+///     .with_snap_points([0.0, 1.0]);
+///
+/// let progress = drawer.evaluate(window, cx);
+///
+/// div()
+///     .left(px(-240.).lerp(&px(0.), progress))
+///     .on_drag_move::<DrawerDrag>({
+///         let drawer = drawer.clone();
+///         move |event, window, cx| {
+///             drawer.drag(cx, event.event.position.x / px(240.));
+///             window.refresh();
+///         }
+///     })
+///     .on_mouse_up(MouseButton::Left, {
+///         let drawer = drawer.clone();
+///         move |_event, window, cx| {
+///             drawer.release(cx);
+///             window.refresh();
+///         }
+///     })
+/// ```
+#[derive(Clone)]
+pub struct GestureTransition {
+    /// The progress values that a release can settle on.
+    snap_points: Vec<f32>,
+
+    /// How the progress moves to its snap point once released.
+    release: GestureRelease,
+
+    /// How far ahead the release velocity is projected when picking a snap point.
+    projection: Duration,
+
+    /// Whether the release ignores the reduced motion of the [`MotionSettings`].
+    essential: bool,
+
+    state: Entity<GestureState>,
+
+    /// A cached version of the progress.
+    cached_value: RefCell<Option<f32>>,
+}
+
+impl GestureTransition {
+    /// Create a new gesture transition using the specified state.
+    ///
+    /// It snaps to 0 or 1 when released, with a spring, and projects the release
+    /// velocity 150ms ahead.
+    pub fn new(state: Entity<GestureState>) -> Self {
+        Self {
+            snap_points: vec![0., 1.],
+            release: GestureRelease::default(),
+            projection: Duration::from_millis(150),
+            essential: false,
+            state,
+            cached_value: RefCell::new(None),
+        }
+    }
+
+    /// Sets the progress values that a release can settle on.
+    ///
+    /// Without any snap points, a release coasts to wherever its velocity projects
+    /// the progress.
+    pub fn with_snap_points(mut self, snap_points: impl IntoIterator<Item = f32>) -> Self {
+        self.snap_points = snap_points.into_iter().collect();
+        self
+    }
+
+    /// Sets how the progress moves to its snap point once released.
+    pub fn with_release(mut self, release: GestureRelease) -> Self {
+        self.release = release;
+        self
+    }
+
+    /// Sets how far ahead the release velocity is projected when picking a snap
+    /// point. Longer projections make flicks more likely to carry over to the
+    /// next snap point.
+    pub fn with_projection(mut self, projection: Duration) -> Self {
+        self.projection = projection;
+        self
+    }
+
+    /// Sets whether the release is essential and should move to its snap point
+    /// even when the app-wide [`MotionSettings`] reduce motion. The time scale
+    /// still applies. Drags always follow the pointer.
+    pub fn essential(mut self, essential: bool) -> Self {
+        self.essential = essential;
+        self
+    }
+
+    /// Moves the progress to follow a drag.
+    ///
+    /// The first call after the transition was released (or created) starts a new
+    /// drag, interrupting any motion toward a snap point.
+    pub fn drag(&self, cx: &mut App, progress: f32) {
+        self.state.update(cx, |state, _cx| {
+            let now = state.clock.now();
+
+            if !state.dragging {
+                state.dragging = true;
+                state.settle = None;
+                state.samples.clear();
+            }
+
+            state.progress = progress;
+            state.samples.push_back((now, progress));
+
+            // Keep one sample older than the window, to measure across all of it.
+            while state.samples.len() > 2
+                && now.saturating_duration_since(state.samples[1].0) > VELOCITY_WINDOW
+            {
+                state.samples.pop_front();
+            }
+
+            state.velocity = state.drag_velocity(now);
+        });
+        *self.cached_value.borrow_mut() = None;
+    }
+
+    /// Ends the drag, and starts moving toward the snap point closest to where
+    /// the drag's velocity would carry the progress.
+    ///
+    /// Returns the snap point that the progress is moving to.
+    pub fn release(&self, cx: &mut App) -> f32 {
+        let (progress, velocity) = self.state.update(cx, |state, _cx| {
+            let velocity = state.drag_velocity(state.clock.now());
+            state.dragging = false;
+            state.samples.clear();
+            (state.progress, velocity)
+        });
+
+        let projected = progress + velocity * self.projection.as_secs_f32();
+        let target = self
+            .snap_points
+            .iter()
+            .copied()
+            .min_by(|a, b| (a - projected).abs().total_cmp(&(b - projected).abs()))
+            .unwrap_or(projected);
+
+        self.settle_with_velocity(cx, target, velocity);
+        target
+    }
+
+    /// Moves the progress to the given target, as if it had been released
+    /// toward it, keeping the current velocity. This also ends any drag.
+    pub fn animate_to(&self, cx: &mut App, target: f32) {
+        let motion = MotionSettings::get(cx);
+
+        self.state.update(cx, |state, _cx| {
+            self.step(state, &motion);
+            state.dragging = false;
+            state.samples.clear();
+        });
+
+        let velocity = self.state.read(cx).velocity;
+        self.settle_with_velocity(cx, target, velocity);
+    }
+
+    fn settle_with_velocity(&self, cx: &mut App, target: f32, velocity: f32) {
+        self.state.update(cx, |state, _cx| {
+            let now = state.clock.now();
+
+            state.velocity = velocity;
+            state.settle = Some(Settle {
+                target,
+                from: state.progress,
+                from_velocity: velocity,
+                elapsed_secs: 0.,
+                last_stepped_at: now,
+            });
+        });
+        *self.cached_value.borrow_mut() = None;
+    }
+
+    /// Moves the progress toward its snap point up to the current time, at the
+    /// rate given by the motion settings. Returns if the progress is still moving.
+    fn step(&self, state: &mut GestureState, motion: &MotionSettings) -> bool {
+        let Some(settle) = state.settle.as_mut() else {
+            return false;
+        };
+
+        let now = state.clock.now();
+
+        let natural_secs = match self.release {
+            GestureRelease::Spring {
+                stiffness,
+                damping,
+                mass,
+            } => settle_secs(stiffness, damping, mass),
+            GestureRelease::Duration(duration) => duration.as_secs_f32(),
+        };
+        let rate = motion.playback_rate(natural_secs, self.essential);
+
+        let step_secs = now
+            .saturating_duration_since(settle.last_stepped_at)
+            .as_secs_f32()
+            * rate.unwrap_or(0.);
+        settle.elapsed_secs += step_secs;
+        settle.last_stepped_at = now;
+
+        let moving = match self.release {
+            _ if rate.is_none() => false,
+            GestureRelease::Spring {
+                stiffness,
+                damping,
+                mass,
+            } => {
+                let mut remaining_secs = step_secs;
+                let mut steps = 0;
+                let mut moving = true;
+
+                while remaining_secs > 0. && steps < MAX_STEPS {
+                    let dt = remaining_secs.min(STEP_SECS);
+
+                    let spring_force = -stiffness * (state.progress - settle.target);
+                    let damping_force = -damping * state.velocity;
+
+                    state.velocity += (spring_force + damping_force) / mass * dt;
+                    state.progress += state.velocity * dt;
+
+                    remaining_secs -= dt;
+                    steps += 1;
+
+                    if (state.progress - settle.target).abs() <= REST_THRESHOLD
+                        && state.velocity.abs() <= REST_THRESHOLD
+                    {
+                        moving = false;
+                        break;
+                    }
+                }
+
+                moving
+            }
+            GestureRelease::Duration(duration) => {
+                let duration_secs = duration.as_secs_f32();
+                let t = if duration_secs > 0. {
+                    settle.elapsed_secs / duration_secs
+                } else {
+                    1.
+                };
+
+                if t < 1. {
+                    let (progress, velocity) = hermite(settle, duration_secs, t);
+                    state.progress = progress;
+                    state.velocity = velocity;
+                }
+
+                t < 1.
+            }
+        };
+
+        if !moving {
+            state.progress = settle.target;
+            state.velocity = 0.;
+            state.settle = None;
+        }
+
+        moving
+    }
+
+    /// Evaluates the progress without using the cache.
+    /// Returns if the progress is moving toward a snap point (bool) and the
+    /// progress (f32).
+    fn raw_evaluate(&self, cx: &mut App) -> (bool, f32) {
+        let motion = MotionSettings::get(cx);
+        let mut state_entity = self.state.as_mut(cx);
+        let state: &mut GestureState = state_entity.borrow_mut();
+
+        // A frozen settle doesn't move, so it doesn't need animation frames.
+        let moving = self.step(state, &motion) && motion.time_scale() > 0.;
+        (moving, state.progress)
+    }
+
+    /// Evaluates and returns the current progress.
+    ///
+    /// While released, this moves the progress toward its snap point and, until
+    /// it gets there, requests animation frames. While dragging, the progress only
+    /// changes through [`drag`](Self::drag), so no frames are requested.
+    ///
+    /// The returned value is cached for the duration of the current frame.
+    pub fn evaluate(&self, window: &mut Window, cx: &mut App) -> f32 {
+        if let Some(progress) = *self.cached_value.borrow() {
+            return progress;
+        }
+
+        self.state.read(cx).clock.begin_frame(window);
+
+        let (moving, progress) = self.raw_evaluate(cx);
+
+        if moving {
+            window.request_animation_frame();
+        }
+
+        *self.cached_value.borrow_mut() = Some(progress);
+        progress
+    }
+
+    /// Returns the current velocity, in progress per second.
+    pub fn velocity(&self, cx: &App) -> f32 {
+        self.state.read(cx).velocity
+    }
+
+    /// Returns whether the transition is being dragged.
+    pub fn is_dragging(&self, cx: &App) -> bool {
+        self.state.read(cx).dragging
+    }
+
+    /// Returns the snap point that a released transition is moving to, if it
+    /// hasn't reached it yet.
+    pub fn read_target(&self, cx: &App) -> Option<f32> {
+        self.state.read(cx).settle.map(|settle| settle.target)
+    }
+
+    /// Replaces the clock that this transition's state reads the current time from.
+    pub fn set_clock(&self, cx: &mut App, clock: impl Clock + 'static) {
+        self.state.update(cx, |state, _cx| {
+            state.clock = Rc::new(clock);
+        });
+    }
+
+    /// Returns the entity ID associated with this transition's state.
+    pub fn entity_id(&self) -> EntityId {
+        self.state.entity_id()
+    }
+
+    /// Resets the transition to rest on its initial progress, ending any drag.
+    ///
+    /// The cache is also cleared.
+    pub fn reset(&self, cx: &mut App) {
+        self.state.update(cx, |state, _cx| {
+            state.progress = state.initial_progress;
+            state.velocity = 0.;
+            state.dragging = false;
+            state.samples.clear();
+            state.settle = None;
+        });
+        *self.cached_value.borrow_mut() = None;
+    }
+}
+
+/// Evaluates the cubic Hermite curve from the settle's start to its target at
+/// `t`, which starts with the release velocity and ends at rest. Returns the
+/// progress and the velocity in progress per second.
+fn hermite(settle: &Settle, duration_secs: f32, t: f32) -> (f32, f32) {
+    let (t2, t3) = (t * t, t * t * t);
+    let start_tangent = settle.from_velocity * duration_secs;
+
+    let progress = (2. * t3 - 3. * t2 + 1.) * settle.from
+        + (t3 - 2. * t2 + t) * start_tangent
+        + (-2. * t3 + 3. * t2) * settle.target;
+
+    let derivative = (6. * t2 - 6. * t) * settle.from
+        + (3. * t2 - 4. * t + 1.) * start_tangent
+        + (-6. * t2 + 6. * t) * settle.target;
+
+    (progress, derivative / duration_secs)
+}
+
+#[cfg(all(test, feature = "test-support"))]
+mod tests {
+    use super::*;
+    use crate::{ManualClock, ReducedMotion};
+    use gpui::{AppContext, TestAppContext};
+
+    fn create_gesture(cx: &mut App, clock: &ManualClock) -> GestureTransition {
+        let state = cx.new(|_| GestureState::new(0.).with_clock(clock.clone()));
+        GestureTransition::new(state)
+    }
+
+    /// Drags from `from` to `to` in steps of 10ms.
+    fn drag(gesture: &GestureTransition, cx: &mut App, clock: &ManualClock, from: f32, to: f32) {
+        for step in 0..=10 {
+            gesture.drag(cx, from + (to - from) * step as f32 / 10.);
+            clock.advance(Duration::from_millis(10));
+        }
+    }
+
+    #[gpui::test]
+    fn test_drag_sets_progress_and_velocity(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let clock = ManualClock::new();
+            let gesture = create_gesture(cx, &clock);
+
+            drag(&gesture, cx, &clock, 0., 0.3);
+
+            assert!(gesture.is_dragging(cx));
+            assert_eq!(gesture.raw_evaluate(cx), (false, 0.3));
+            // 0.3 over 100ms.
+            assert!((gesture.velocity(cx) - 3.).abs() < 0.01);
+        });
+    }
+
+    #[gpui::test]
+    fn test_slow_release_snaps_to_nearest(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let clock = ManualClock::new();
+            let gesture = create_gesture(cx, &clock);
+
+            drag(&gesture, cx, &clock, 0.3, 0.3);
+            assert_eq!(gesture.release(cx), 0.);
+            assert!(!gesture.is_dragging(cx));
+
+            clock.advance(Duration::from_secs(2));
+            assert_eq!(gesture.raw_evaluate(cx), (false, 0.));
+            assert_eq!(gesture.read_target(cx), None);
+        });
+    }
+
+    #[gpui::test]
+    fn test_flick_carries_to_next_snap_point(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let clock = ManualClock::new();
+            let gesture = create_gesture(cx, &clock);
+
+            drag(&gesture, cx, &clock, 0., 0.3);
+            assert_eq!(gesture.release(cx), 1.);
+
+            // The progress keeps moving in the direction of the drag.
+            clock.advance(Duration::from_millis(16));
+            let (moving, progress) = gesture.raw_evaluate(cx);
+            assert!(moving);
+            assert!(progress > 0.3);
+
+            clock.advance(Duration::from_secs(2));
+            assert_eq!(gesture.raw_evaluate(cx), (false, 1.));
+        });
+    }
+
+    #[gpui::test]
+    fn test_holding_still_before_release_drops_velocity(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let clock = ManualClock::new();
+            let gesture = create_gesture(cx, &clock);
+
+            drag(&gesture, cx, &clock, 0., 0.3);
+            clock.advance(Duration::from_millis(500));
+
+            assert_eq!(gesture.release(cx), 0.);
+        });
+    }
+
+    #[gpui::test]
+    fn test_duration_release(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let clock = ManualClock::new();
+            let state = cx.new(|_| GestureState::new(0.).with_clock(clock.clone()));
+            let gesture = GestureTransition::new(state)
+                .with_snap_points([0., 0.5, 1.])
+                .with_release(GestureRelease::Duration(Duration::from_millis(200)));
+
+            drag(&gesture, cx, &clock, 0.3, 0.4);
+            assert_eq!(gesture.release(cx), 0.5);
+
+            // Starts out with the drag's velocity.
+            assert!((gesture.velocity(cx) - 1.).abs() < 0.01);
+
+            clock.advance(Duration::from_millis(100));
+            let (moving, progress) = gesture.raw_evaluate(cx);
+            assert!(moving);
+            assert!(progress > 0.4 && progress < 0.5);
+
+            clock.advance(Duration::from_millis(100));
+            assert_eq!(gesture.raw_evaluate(cx), (false, 0.5));
+            assert_eq!(gesture.velocity(cx), 0.);
+        });
+    }
+
+    #[gpui::test]
+    fn test_release_follows_motion_settings(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let clock = ManualClock::new();
+            let state = cx.new(|_| GestureState::new(0.).with_clock(clock.clone()));
+            let gesture = GestureTransition::new(state)
+                .with_release(GestureRelease::Duration(Duration::from_millis(200)));
+
+            // At half speed, the release is only halfway there after 200ms.
+            MotionSettings::set(MotionSettings::default().with_time_scale(0.5), cx);
+            gesture.animate_to(cx, 1.);
+            clock.advance(Duration::from_millis(200));
+            let (moving, progress) = gesture.raw_evaluate(cx);
+            assert!(moving);
+            assert!((progress - 0.5).abs() < 0.001);
+
+            // Reduced motion snaps releases to their target, except essential ones.
+            MotionSettings::set(
+                MotionSettings::default().with_reduced_motion(Some(ReducedMotion::Snap)),
+                cx,
+            );
+            assert_eq!(gesture.raw_evaluate(cx), (false, 1.));
+
+            let essential = gesture.clone().essential(true);
+            essential.animate_to(cx, 0.);
+            clock.advance(Duration::from_millis(100));
+            assert!(essential.raw_evaluate(cx).0);
+
+            // Shortened releases reach their target within the maximum duration.
+            MotionSettings::set(
+                MotionSettings::default()
+                    .with_reduced_motion(Some(ReducedMotion::Shorten(Duration::from_millis(50)))),
+                cx,
+            );
+            gesture.animate_to(cx, 1.);
+            clock.advance(Duration::from_millis(50));
+            assert_eq!(gesture.raw_evaluate(cx), (false, 1.));
+        });
+    }
+
+    #[gpui::test]
+    fn test_drag_interrupts_release(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let clock = ManualClock::new();
+            let gesture = create_gesture(cx, &clock);
+
+            drag(&gesture, cx, &clock, 0., 0.3);
+            gesture.release(cx);
+            clock.advance(Duration::from_millis(16));
+
+            gesture.drag(cx, 0.2);
+            clock.advance(Duration::from_millis(100));
+            assert_eq!(gesture.raw_evaluate(cx), (false, 0.2));
+            assert_eq!(gesture.read_target(cx), None);
+        });
+    }
+
+    #[gpui::test]
+    fn test_animate_to_and_reset(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let clock = ManualClock::new();
+            let gesture = create_gesture(cx, &clock);
+
+            gesture.animate_to(cx, 1.);
+            assert_eq!(gesture.read_target(cx), Some(1.));
+
+            clock.advance(Duration::from_secs(2));
+            assert_eq!(gesture.raw_evaluate(cx), (false, 1.));
+
+            gesture.reset(cx);
+            assert_eq!(gesture.raw_evaluate(cx), (false, 0.));
+        });
+    }
+}
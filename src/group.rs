@@ -0,0 +1,544 @@
+//! Staggered transitions for groups of items, such as the rows of a list.
+
+use std::{
+    collections::HashMap,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use gpui::{App, AppContext, Context, ElementId, Entity, EntityId, Window, linear};
+
+use crate::{Clock, Lerp, SystemClock, Transition, TransitionState};
+
+/// An item of a [`TransitionGroup`].
+#[derive(Clone)]
+struct GroupItem<T: Lerp + Clone + PartialEq + 'static> {
+    /// The pass in which the item was last evaluated.
+    pass: usize,
+
+    /// The position of the item in that pass.
+    index: usize,
+
+    /// Whether the item was evaluated since the goal last changed.
+    seen: bool,
+
+    /// The stagger delay of the item, fixed when its goal last changed.
+    delay: Duration,
+
+    state: Entity<TransitionState<T>>,
+}
+
+/// State container for a [`TransitionGroup`].
+///
+/// Holds the goal shared by every item in the group, along with the state of
+/// each item's transition, keyed by item.
+pub struct TransitionGroupState<T: Lerp + Clone + PartialEq + 'static> {
+    initial_goal: T,
+    goal: T,
+    /// When the shared goal last changed.
+    goal_changed_at: Option<Instant>,
+    items: HashMap<ElementId, GroupItem<T>>,
+    /// The current pass over the items, which usually matches a render.
+    pass: usize,
+    /// How many items were evaluated in the current pass.
+    pass_len: usize,
+    /// The item that was evaluated last.
+    last_visited: Option<ElementId>,
+    clock: Rc<dyn Clock>,
+}
+
+impl<T: Lerp + Clone + PartialEq + 'static> TransitionGroupState<T> {
+    /// Creates a new group state with the given initial goal.
+    pub fn new(initial_goal: T) -> Self {
+        Self {
+            goal: initial_goal.clone(),
+            initial_goal,
+            goal_changed_at: None,
+            items: HashMap::new(),
+            pass: 0,
+            pass_len: 0,
+            last_visited: None,
+            clock: Rc::new(SystemClock),
+        }
+    }
+
+    /// Sets the clock used by every item in the group.
+    ///
+    /// Defaults to [`SystemClock`]. Use a [`ManualClock`](crate::ManualClock) to step
+    /// the group deterministically.
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Rc::new(clock);
+        self
+    }
+
+    /// Records that the item with the given key was evaluated, giving it the next
+    /// position in the current pass. Evaluating an item again after other items
+    /// starts a new pass, as the list is rendered again, while evaluating it
+    /// several times in a row only counts once.
+    fn visit(&mut self, key: &ElementId) {
+        let Some(item) = self.items.get_mut(key) else {
+            return;
+        };
+
+        item.seen = true;
+        if self.last_visited.as_ref() == Some(key) {
+            return;
+        }
+
+        if item.pass == self.pass {
+            self.pass += 1;
+            self.pass_len = 0;
+        }
+
+        item.pass = self.pass;
+        item.index = self.pass_len;
+        self.pass_len += 1;
+        self.last_visited = Some(key.clone());
+    }
+
+    /// Drops the items that weren't evaluated since the goal last changed, as long
+    /// as any item was, and returns the rest by their position in the latest
+    /// pass, followed by the items that were left out of it.
+    fn prune_and_order(&mut self) -> Vec<&mut GroupItem<T>> {
+        if self.items.values().any(|item| item.seen) {
+            self.items.retain(|_, item| item.seen);
+        }
+
+        let pass = self.pass;
+        let mut items = self.items.values_mut().collect::<Vec<_>>();
+        items.sort_unstable_by_key(|item| (item.pass != pass, item.pass, item.index));
+
+        for item in &mut items {
+            item.seen = false;
+        }
+
+        items
+    }
+}
+
+/// A group of transitions, keyed by item, that share a goal and start one after
+/// another.
+///
+/// Each item gets its own [`Transition`], created the first time the item is
+/// evaluated. When the group's goal changes, every item animates towards it,
+/// with each item's start delayed according to its index in the group. Items are
+/// indexed by their position in the latest render, that is the order in which
+/// they were last evaluated. Each item's delay is fixed when the goal changes, so
+/// adding or removing items doesn't affect animations that are already running.
+///
+/// Items that join the group after its goal has changed animate from the
+/// group's initial goal to the current one, so rows added to a list that has
+/// already appeared animate in as well. They are indexed by their position in
+/// the render they join in, and start when they would have started had they
+/// been part of the group when the goal changed, or straight away if that time
+/// has passed.
+///
+/// Items that weren't evaluated since the goal last changed are dropped from the
+/// group when it changes again, so the group doesn't grow as keys come and go.
+/// [`remove`](Self::remove) drops an item straight away.
+///
+/// # Example
+///
+/// ```ignore
+/// let rows = window
+///     .use_keyed_transition_group("rows", cx, Duration::from_millis(250), |_, _| 0.0_f32)
+///     .with_stagger(Duration::from_millis(40));
+///
+/// for item in &self.items {
+///     let opacity = rows.evaluate(item.id.clone(), window, cx);
+///     // ...
+/// }
+///
+/// // Fade every row in, one after another.
+/// rows.update(cx, |opacity, cx| {
+///     *opacity = 1.0;
+///     cx.notify();
+/// });
+/// ```
+#[derive(Clone)]
+pub struct TransitionGroup<T: Lerp + Clone + PartialEq + 'static> {
+    duration: Duration,
+    easing: Rc<dyn Fn(f32) -> f32>,
+
+    /// Returns the delay of the item at the given index, given the number of items.
+    stagger: Rc<dyn Fn(usize, usize) -> Duration>,
+
+    state: Entity<TransitionGroupState<T>>,
+}
+
+impl<T: Lerp + Clone + PartialEq + 'static> TransitionGroup<T> {
+    /// Creates a new transition group with the given duration for each item.
+    pub fn new(state: Entity<TransitionGroupState<T>>, duration: Duration) -> Self {
+        Self {
+            duration,
+            easing: Rc::new(linear),
+            stagger: Rc::new(|_, _| Duration::ZERO),
+            state,
+        }
+    }
+
+    /// Sets the easing function used by every item.
+    pub fn with_easing(mut self, easing: impl Fn(f32) -> f32 + 'static) -> Self {
+        self.easing = Rc::new(easing);
+        self
+    }
+
+    /// Delays each item by `step` more than the one before it.
+    pub fn with_stagger(self, step: Duration) -> Self {
+        self.with_stagger_fn(move |index, _count| step * index as u32)
+    }
+
+    /// Sets a function that returns the delay of each item, given its index and
+    /// the number of items in the group when the goal changed.
+    ///
+    /// This allows for other orderings, such as staggering from the last item:
+    ///
+    /// ```ignore
+    /// group.with_stagger_fn(|index, count| Duration::from_millis(40) * (count - 1 - index) as u32)
+    /// ```
+    pub fn with_stagger_fn(mut self, stagger: impl Fn(usize, usize) -> Duration + 'static) -> Self {
+        self.stagger = Rc::new(stagger);
+        self
+    }
+
+    fn transition(&self, state: Entity<TransitionState<T>>, delay: Duration) -> Transition<T> {
+        let easing = self.easing.clone();
+
+        Transition::new(state, self.duration)
+            .with_easing(move |delta| easing(delta))
+            .with_delay(delay)
+    }
+
+    /// Returns the transition of the item with the given key, adding the item to
+    /// the group if it isn't part of it yet.
+    pub fn item(&self, key: impl Into<ElementId>, cx: &mut App) -> Transition<T> {
+        let key = key.into();
+
+        let existing = self.state.update(cx, |group, _cx| {
+            group.visit(&key);
+            group
+                .items
+                .get(&key)
+                .map(|item| (item.state.clone(), item.delay))
+        });
+
+        if let Some((state, delay)) = existing {
+            return self.transition(state, delay);
+        }
+
+        let group = self.state.read(cx);
+        let initial_goal = group.initial_goal.clone();
+        let goal = group.goal.clone();
+        let clock = group.clock.clone();
+
+        // The item is indexed by its position in the current pass, and catches up
+        // with the stagger of the animation that is already running, if any.
+        let index = group.pass_len;
+        let count = group.items.len().max(index) + 1;
+        let since_goal_changed = group
+            .goal_changed_at
+            .map(|changed_at| clock.now().saturating_duration_since(changed_at))
+            .unwrap_or_default();
+        let delay = (self.stagger)(index, count).saturating_sub(since_goal_changed);
+
+        let state = cx.new(|_cx| {
+            let mut state = TransitionState::new(initial_goal);
+            state.clock = clock;
+            state
+        });
+
+        self.state.update(cx, |group, _cx| {
+            // Joining never starts a new pass, so the item is placed in this one.
+            group.items.insert(
+                key.clone(),
+                GroupItem {
+                    pass: usize::MAX,
+                    index: 0,
+                    seen: false,
+                    delay,
+                    state: state.clone(),
+                },
+            );
+            group.visit(&key);
+        });
+
+        let transition = self.transition(state, delay);
+        transition.update(cx, |item_goal, _cx| *item_goal = goal);
+        transition
+    }
+
+    /// Evaluates and returns the current value of the item with the given key.
+    ///
+    /// See [`Transition::evaluate`].
+    pub fn evaluate(&self, key: impl Into<ElementId>, window: &mut Window, cx: &mut App) -> T {
+        self.item(key, cx).evaluate(window, cx).clone()
+    }
+
+    /// Reads the goal shared by every item in the group.
+    pub fn read_goal<'b>(&'b self, cx: &'b mut App) -> &'b T {
+        &self.state.read(cx).goal
+    }
+
+    /// Updates the goal shared by every item in the group.
+    ///
+    /// If the goal changes, every item starts animating towards it after its
+    /// stagger delay. Returns `true` if the goal was updated.
+    ///
+    /// Note: This method does not automatically notify GPUI of changes. You should
+    /// call `cx.notify()` within the closure if you want to trigger a re-render.
+    pub fn update<R>(
+        &self,
+        cx: &mut App,
+        update: impl FnOnce(&mut T, &mut Context<TransitionGroupState<T>>) -> R,
+    ) -> bool {
+        let changed = self.state.update(cx, |group, cx| {
+            let last_goal = group.goal.clone();
+            update(&mut group.goal, cx);
+
+            if group.goal == last_goal {
+                return None;
+            }
+
+            group.goal_changed_at = Some(group.clock.now());
+
+            // Fix the delay of every item, from its position in the group.
+            let mut items = group.prune_and_order();
+
+            let count = items.len();
+            for (index, item) in items.iter_mut().enumerate() {
+                item.delay = (self.stagger)(index, count);
+            }
+
+            let items = items
+                .into_iter()
+                .map(|item| item.clone())
+                .collect::<Vec<_>>();
+            Some((group.goal.clone(), items))
+        });
+
+        let Some((goal, items)) = changed else {
+            return false;
+        };
+
+        for item in items {
+            self.transition(item.state, item.delay)
+                .update(cx, |item_goal, _cx| *item_goal = goal.clone());
+        }
+
+        true
+    }
+
+    /// Removes the item with the given key from the group straight away, rather
+    /// than the next time the goal changes.
+    ///
+    /// Items that are animating keep their delay.
+    pub fn remove(&self, key: impl Into<ElementId>, cx: &mut App) {
+        let key = key.into();
+
+        self.state.update(cx, |group, _cx| {
+            group.items.remove(&key);
+            if group.last_visited.as_ref() == Some(&key) {
+                group.last_visited = None;
+            }
+        });
+    }
+
+    /// Replaces the clock used by the group and every item in it.
+    pub fn set_clock(&self, cx: &mut App, clock: impl Clock + 'static) {
+        let clock: Rc<dyn Clock> = Rc::new(clock);
+
+        let items = self.state.update(cx, |group, _cx| {
+            group.clock = clock.clone();
+            group.items.values().cloned().collect::<Vec<_>>()
+        });
+
+        for GroupItem { state, .. } in items {
+            state.update(cx, |state, _cx| state.clock = clock.clone());
+        }
+    }
+
+    /// Returns the entity ID associated with this group's state.
+    pub fn entity_id(&self) -> EntityId {
+        self.state.entity_id()
+    }
+
+    /// Resets the group to its initial goal and removes every item from it.
+    pub fn reset(&self, cx: &mut App) {
+        self.state.update(cx, |group, cx| {
+            group.goal = group.initial_goal.clone();
+            group.goal_changed_at = None;
+            group.items.clear();
+            group.pass_len = 0;
+            group.last_visited = None;
+            cx.notify();
+        });
+    }
+}
+
+#[cfg(all(test, feature = "test-support"))]
+mod tests {
+    use super::*;
+    use crate::ManualClock;
+    use gpui::TestAppContext;
+
+    fn create_group(cx: &mut App, clock: &ManualClock) -> TransitionGroup<f32> {
+        let state = cx.new(|_| TransitionGroupState::new(0.0_f32).with_clock(clock.clone()));
+        TransitionGroup::new(state, Duration::from_millis(100))
+            .with_stagger(Duration::from_millis(50))
+    }
+
+    fn value(group: &TransitionGroup<f32>, key: &'static str, cx: &mut App) -> f32 {
+        group.item(key, cx).raw_evaluate(cx).1
+    }
+
+    #[gpui::test]
+    fn test_group_staggers_items(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let clock = ManualClock::new();
+            let group = create_group(cx, &clock);
+
+            for key in ["a", "b", "c"] {
+                group.item(key, cx);
+            }
+
+            assert!(group.update(cx, |goal, _cx| *goal = 100.0));
+
+            clock.advance(Duration::from_millis(50));
+            assert!((value(&group, "a", cx) - 50.0).abs() < 0.001);
+            assert_eq!(value(&group, "b", cx), 0.0);
+            assert_eq!(value(&group, "c", cx), 0.0);
+
+            clock.advance(Duration::from_millis(50));
+            assert_eq!(value(&group, "a", cx), 100.0);
+            assert!((value(&group, "b", cx) - 50.0).abs() < 0.001);
+            assert_eq!(value(&group, "c", cx), 0.0);
+
+            clock.advance(Duration::from_millis(100));
+            assert_eq!(value(&group, "c", cx), 100.0);
+        });
+    }
+
+    #[gpui::test]
+    fn test_group_stagger_fn(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let clock = ManualClock::new();
+            let group = create_group(cx, &clock).with_stagger_fn(|index, count| {
+                Duration::from_millis(50) * (count - 1 - index) as u32
+            });
+
+            group.item("a", cx);
+            group.item("b", cx);
+            group.update(cx, |goal, _cx| *goal = 100.0);
+
+            clock.advance(Duration::from_millis(50));
+            assert_eq!(value(&group, "a", cx), 0.0);
+            assert!((value(&group, "b", cx) - 50.0).abs() < 0.001);
+        });
+    }
+
+    #[gpui::test]
+    fn test_group_late_item_animates_to_goal(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let clock = ManualClock::new();
+            let group = create_group(cx, &clock);
+
+            group.item("a", cx);
+            group.update(cx, |goal, _cx| *goal = 100.0);
+            clock.advance(Duration::from_millis(500));
+
+            // "b" joins as the second item, long after it would have started.
+            assert_eq!(value(&group, "b", cx), 0.0);
+            clock.advance(Duration::from_millis(50));
+            assert!((value(&group, "b", cx) - 50.0).abs() < 0.001);
+
+            // "c" joins as the third item, during the stagger of a new goal, and
+            // starts one step after "b".
+            group.update(cx, |goal, _cx| *goal = 200.0);
+            clock.advance(Duration::from_millis(25));
+            assert_eq!(value(&group, "c", cx), 0.0);
+            clock.advance(Duration::from_millis(125));
+            assert!((value(&group, "c", cx) - 100.0).abs() < 0.001);
+        });
+    }
+
+    #[gpui::test]
+    fn test_group_delays_fixed_while_animating(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let clock = ManualClock::new();
+            let group = create_group(cx, &clock).with_stagger_fn(|index, count| {
+                Duration::from_millis(50) * (count - 1 - index) as u32
+            });
+
+            group.item("a", cx);
+            group.item("b", cx);
+            group.update(cx, |goal, _cx| *goal = 100.0);
+
+            // Neither removing nor adding items moves the running animations.
+            group.remove("b", cx);
+            group.item("c", cx);
+            clock.advance(Duration::from_millis(100));
+            assert!((value(&group, "a", cx) - 50.0).abs() < 0.001);
+        });
+    }
+
+    #[gpui::test]
+    fn test_group_orders_and_prunes_by_latest_render(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let clock = ManualClock::new();
+            let group = create_group(cx, &clock);
+
+            for key in ["a", "b", "c", "d"] {
+                group.item(key, cx);
+            }
+            group.update(cx, |goal, _cx| *goal = 100.0);
+            clock.advance(Duration::from_millis(500));
+
+            // The list is rendered again without "a" and "d", and with "c" moved up.
+            for key in ["c", "b"] {
+                group.item(key, cx);
+            }
+            group.update(cx, |goal, _cx| *goal = 0.0);
+            assert_eq!(group.state.read(cx).items.len(), 2);
+
+            // "c" is now the first item, so it starts straight away.
+            clock.advance(Duration::from_millis(50));
+            assert!((value(&group, "c", cx) - 50.0).abs() < 0.001);
+            assert_eq!(value(&group, "b", cx), 100.0);
+        });
+    }
+
+    #[gpui::test]
+    fn test_group_unchanged_goal(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let clock = ManualClock::new();
+            let group = create_group(cx, &clock);
+
+            group.item("a", cx);
+            assert!(!group.update(cx, |goal, _cx| *goal = 0.0));
+
+            let (in_progress, _) = group.item("a", cx).raw_evaluate(cx);
+            assert!(!in_progress);
+        });
+    }
+
+    #[gpui::test]
+    fn test_group_remove_and_reset(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let clock = ManualClock::new();
+            let group = create_group(cx, &clock);
+
+            group.item("a", cx);
+            group.item("b", cx);
+            group.remove("a", cx);
+            group.update(cx, |goal, _cx| *goal = 100.0);
+
+            // "b" is now the first item, so it starts straight away.
+            clock.advance(Duration::from_millis(50));
+            assert!((value(&group, "b", cx) - 50.0).abs() < 0.001);
+
+            group.reset(cx);
+            assert_eq!(*group.read_goal(cx), 0.0);
+            assert_eq!(value(&group, "b", cx), 0.0);
+        });
+    }
+}
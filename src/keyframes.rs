@@ -0,0 +1,601 @@
+//! Multi-stop animations with per-segment easing.
+
+use std::{
+    borrow::BorrowMut,
+    cell::{Ref, RefCell},
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use gpui::{App, Context, Entity, EntityId, Window, linear};
+
+use crate::{Clock, Lerp, MotionSettings, SystemClock, motion::scale_elapsed};
+
+/// A single stop in a [`Keyframes`] track.
+///
+/// Two keyframes are equal when their offsets and values are equal. Easing
+/// functions can't be compared, so they are left out, which lets a track that is
+/// rebuilt on every render compare equal to the previous one.
+#[derive(Clone)]
+pub struct Keyframe<T> {
+    /// Where in the track (between 0 and 1) this keyframe sits.
+    pub offset: f32,
+
+    /// The value of the track at this keyframe.
+    pub value: T,
+
+    /// The easing function used for the segment leading up to this keyframe.
+    pub easing: Rc<dyn Fn(f32) -> f32>,
+}
+
+impl<T: PartialEq> PartialEq for Keyframe<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.offset == other.offset && self.value == other.value
+    }
+}
+
+/// A track of values placed at normalized offsets, with a different easing
+/// function between each stop.
+///
+/// Keyframes are kept sorted by offset. Evaluating the track at a time between
+/// two keyframes interpolates between their values using the easing function of
+/// the later keyframe. The track always starts with a keyframe at offset 0, and
+/// after its last keyframe it holds the last value.
+///
+/// # Example
+///
+/// ```ignore
+/// use gpui_transitions::Keyframes;
+///
+/// // 0% → 30% overshoot → 100%
+/// let scale = Keyframes::new(0.0_f32)
+///     .with_keyframe(0.3, 1.2, ease_out_quint())
+///     .with_keyframe(1.0, 1.0, ease_in_out);
+///
+/// assert_eq!(scale.evaluate(0.3), 1.2);
+/// ```
+#[derive(Clone, PartialEq)]
+pub struct Keyframes<T: Lerp + Clone + 'static> {
+    keyframes: Vec<Keyframe<T>>,
+}
+
+impl<T: Lerp + Clone + 'static> Keyframes<T> {
+    /// Creates a new track that starts with the given value at offset 0.
+    pub fn new(initial_value: T) -> Self {
+        Self {
+            keyframes: vec![Keyframe {
+                offset: 0.,
+                value: initial_value,
+                easing: Rc::new(linear),
+            }],
+        }
+    }
+
+    /// Adds a keyframe at the given offset (between 0 and 1).
+    ///
+    /// The easing function is used for the segment between the previous keyframe
+    /// and this one. If a keyframe already exists at the same offset, the new one
+    /// is placed after it, producing an instant jump between the two values.
+    pub fn with_keyframe(
+        mut self,
+        offset: f32,
+        value: T,
+        easing: impl Fn(f32) -> f32 + 'static,
+    ) -> Self {
+        self.insert(offset, value, easing);
+        self
+    }
+
+    /// Inserts a keyframe at the given offset (between 0 and 1).
+    ///
+    /// See [`with_keyframe`](Self::with_keyframe). Offsets outside of 0 to 1 are
+    /// clamped, and a NaN offset is treated as 0.
+    pub fn insert(&mut self, offset: f32, value: T, easing: impl Fn(f32) -> f32 + 'static) {
+        let offset = clamp_unit(offset);
+        let index = self.keyframes.partition_point(|k| k.offset <= offset);
+
+        self.keyframes.insert(
+            index,
+            Keyframe {
+                offset,
+                value,
+                easing: Rc::new(easing),
+            },
+        );
+    }
+
+    /// Returns the keyframes in this track, sorted by offset.
+    pub fn keyframes(&self) -> &[Keyframe<T>] {
+        &self.keyframes
+    }
+
+    /// Returns the value of the last keyframe in the track.
+    pub fn last_value(&self) -> &T {
+        &self.keyframes.last().unwrap().value
+    }
+
+    /// Evaluates the track at the given normalized time (between 0 and 1).
+    ///
+    /// Times outside of 0 to 1 are clamped, and a NaN time is treated as 0.
+    pub fn evaluate(&self, time: f32) -> T {
+        let time = clamp_unit(time);
+        let next_index = self.keyframes.partition_point(|k| k.offset <= time);
+
+        let Some(next) = self.keyframes.get(next_index) else {
+            return self.last_value().clone();
+        };
+        let previous = &self.keyframes[next_index - 1];
+
+        let local_time = (time - previous.offset) / (next.offset - previous.offset);
+        let delta = (next.easing)(local_time);
+
+        previous.value.lerp(&next.value, delta)
+    }
+}
+
+/// Clamps `value` between 0 and 1, mapping NaN to 0, which `f32::clamp` would
+/// pass through.
+fn clamp_unit(value: f32) -> f32 {
+    if value.is_nan() {
+        0.
+    } else {
+        value.clamp(0., 1.)
+    }
+}
+
+/// Internal state container for a [`KeyframeTransition`].
+///
+/// This struct holds the keyframe track being played, along with when playback
+/// was last started.
+///
+/// You typically don't need to interact with this type directly. It's created
+/// and managed by [`KeyframeTransition`] and the
+/// [`WindowUseTransition`](crate::WindowUseTransition) methods.
+#[derive(Clone)]
+pub struct KeyframeState<T: Lerp + Clone + PartialEq + 'static> {
+    pub(crate) started_at: Option<Instant>,
+    /// How much of the track had played when `started_at` was last set.
+    pub(crate) elapsed_offset: Duration,
+    /// The rate at which the track is playing, from the [`MotionSettings`].
+    pub(crate) time_scale: f32,
+    pub(crate) initial_keyframes: Keyframes<T>,
+    pub(crate) keyframes: Keyframes<T>,
+    pub(crate) clock: Rc<dyn Clock>,
+}
+
+impl<T: Lerp + Clone + PartialEq + 'static> KeyframeState<T> {
+    /// Creates a new keyframe state with the given track.
+    ///
+    /// The track begins in a "completed" state (resting on its last keyframe)
+    /// until it is updated or restarted.
+    pub fn new(initial_keyframes: Keyframes<T>) -> Self {
+        Self {
+            started_at: None,
+            elapsed_offset: Duration::ZERO,
+            time_scale: 1.,
+            initial_keyframes: initial_keyframes.clone(),
+            keyframes: initial_keyframes,
+            clock: Rc::new(SystemClock),
+        }
+    }
+
+    /// Sets the clock used to read the current time.
+    ///
+    /// Defaults to [`SystemClock`].
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Rc::new(clock);
+        self
+    }
+
+    /// Returns how much of the track has played, scaled by the time scale, or
+    /// `None` if it was never started.
+    pub(crate) fn elapsed(&self) -> Option<Duration> {
+        let started_at = self.started_at?;
+        let since_started = self.clock.now().saturating_duration_since(started_at);
+
+        Some(
+            self.elapsed_offset
+                .saturating_add(scale_elapsed(since_started, self.time_scale)),
+        )
+    }
+
+    /// Changes the rate at which the track plays from now on, keeping the part
+    /// that has played so far.
+    pub(crate) fn set_time_scale(&mut self, time_scale: f32) {
+        if self.time_scale == time_scale {
+            return;
+        }
+
+        if let Some(elapsed) = self.elapsed() {
+            self.elapsed_offset = elapsed;
+            self.started_at = Some(self.clock.now());
+        }
+
+        self.time_scale = time_scale;
+    }
+
+    /// Plays the track from the start at the given time scale.
+    fn start(&mut self, time_scale: f32) {
+        self.started_at = Some(self.clock.now());
+        self.elapsed_offset = Duration::ZERO;
+        self.time_scale = time_scale;
+    }
+}
+
+/// An animated transition that plays a [`Keyframes`] track over a duration.
+///
+/// `KeyframeTransition` mirrors the API of [`Transition`](crate::Transition), but
+/// instead of interpolating from a start goal to an end goal it plays every stop
+/// of its track in order.
+///
+/// # Example
+///
+/// ```ignore
+/// let scale = window.use_keyed_keyframes(
+///     "pop",
+///     cx,
+///     Duration::from_millis(400),
+///     |_, _| Keyframes::new(1.0_f32),
+/// );
+///
+/// // Get the current value of the track
+/// let value = scale.evaluate(window, cx);
+///
+/// // Replace the track and play it from the start
+/// scale.update(cx, |keyframes, cx| {
+///     *keyframes = Keyframes::new(0.0)
+///         .with_keyframe(0.3, 1.2, ease_out_quint())
+///         .with_keyframe(1.0, 1.0, ease_in_out);
+///     cx.notify();
+/// });
+/// ```
+#[derive(Clone)]
+pub struct KeyframeTransition<T: Lerp + Clone + PartialEq + 'static> {
+    /// The amount of time it takes to play the whole track.
+    duration_secs: f32,
+
+    /// Whether the track ignores the reduced motion of the [`MotionSettings`].
+    essential: bool,
+
+    state: Entity<KeyframeState<T>>,
+
+    /// A cached version of the track's value.
+    cached_value: RefCell<Option<T>>,
+}
+
+impl<T: Lerp + Clone + PartialEq + 'static> KeyframeTransition<T> {
+    /// Create a new keyframe transition with the given duration using the specified state.
+    pub fn new(state: Entity<KeyframeState<T>>, duration: Duration) -> Self {
+        Self {
+            duration_secs: duration.as_secs_f32(),
+            essential: false,
+            state,
+            cached_value: RefCell::new(None),
+        }
+    }
+
+    /// Sets whether the track is essential and should play even when the app-wide
+    /// [`MotionSettings`] reduce motion. The time scale still applies.
+    pub fn essential(mut self, essential: bool) -> Self {
+        self.essential = essential;
+        self
+    }
+
+    /// Returns the linear progress (between 0 and 1) through the track.
+    fn progress(&self, state: &KeyframeState<T>, motion: &MotionSettings) -> f32 {
+        let Some(elapsed) = state.elapsed() else {
+            return 1.;
+        };
+
+        let (duration_secs, _) = motion.apply(self.duration_secs, Duration::ZERO, self.essential);
+        if duration_secs <= 0. {
+            return 1.;
+        }
+
+        (elapsed.as_secs_f32() / duration_secs).min(1.)
+    }
+
+    /// Evaluates the value of the track without using the cache.
+    /// Returns if the track is still playing (bool) and the evaluated value (T).
+    fn raw_evaluate(&self, cx: &mut App) -> (bool, T) {
+        let motion = MotionSettings::get(cx);
+        let mut state_entity = self.state.as_mut(cx);
+        let state: &mut KeyframeState<T> = state_entity.borrow_mut();
+
+        state.set_time_scale(motion.time_scale());
+        let progress = self.progress(state, &motion);
+
+        // A frozen track doesn't move, so it doesn't need animation frames.
+        let playing = progress != 1. && motion.time_scale() > 0.;
+        (playing, state.keyframes.evaluate(progress))
+    }
+
+    /// Evaluates and returns the current value of the track.
+    ///
+    /// If the track is still playing, this automatically requests an animation
+    /// frame to continue the animation.
+    ///
+    /// The returned value is cached for the duration of the current frame to avoid
+    /// redundant calculations when called multiple times.
+    pub fn evaluate(&self, window: &mut Window, cx: &mut App) -> Ref<'_, T> {
+        if self.cached_value.borrow().is_none() {
+            self.state.read(cx).clock.begin_frame(window);
+
+            let (in_progress, evaluated_value) = self.raw_evaluate(cx);
+
+            if in_progress {
+                window.request_animation_frame();
+            }
+
+            *self.cached_value.borrow_mut() = Some(evaluated_value);
+        }
+
+        Ref::map(self.cached_value.borrow(), |opt| opt.as_ref().unwrap())
+    }
+
+    /// Evaluates and returns the current linear progress through the track.
+    ///
+    /// A value of 0.0 means playback just started, and 1.0 means it has completed.
+    pub fn evaluate_progress(&self, cx: &App) -> f32 {
+        self.progress(self.state.read(cx), &MotionSettings::get(cx))
+    }
+
+    /// Reads the track being played.
+    pub fn read_keyframes<'b>(&'b self, cx: &'b mut App) -> &'b Keyframes<T> {
+        &self.state.read(cx).keyframes
+    }
+
+    /// Reads the current value of the cached track, if it exists.
+    pub fn read_cache(&self) -> Ref<'_, Option<T>> {
+        self.cached_value.borrow()
+    }
+
+    /// Updates the track and plays it from the start.
+    ///
+    /// The provided closure receives a mutable reference to the current track and
+    /// can modify it. If the track changes, it is played from the start. Use
+    /// [`restart`](Self::restart) to replay a track that hasn't changed, or whose
+    /// easing functions are all that changed.
+    ///
+    /// Returns `true` if the track was actually updated (i.e., the new track
+    /// differs from the previous one), `false` otherwise.
+    ///
+    /// Note: This method does not automatically notify GPUI of changes. You should
+    /// call `cx.notify()` within the closure if you want to trigger a re-render.
+    pub fn update<R>(
+        &self,
+        cx: &mut App,
+        update: impl FnOnce(&mut Keyframes<T>, &mut Context<KeyframeState<T>>) -> R,
+    ) -> bool {
+        let time_scale = MotionSettings::get(cx).time_scale();
+
+        self.state.update(cx, |state, cx| {
+            let last_keyframes = state.keyframes.clone();
+
+            update(&mut state.keyframes, cx);
+
+            if state.keyframes == last_keyframes {
+                return false;
+            }
+
+            state.start(time_scale);
+            true
+        })
+    }
+
+    /// Plays the current track again from the start.
+    pub fn restart(&self, cx: &mut App) {
+        let time_scale = MotionSettings::get(cx).time_scale();
+
+        self.state.update(cx, |state, cx| {
+            state.start(time_scale);
+            cx.notify();
+        });
+    }
+
+    /// Replaces the clock that this track's state reads the current time from.
+    pub fn set_clock(&self, cx: &mut App, clock: impl Clock + 'static) {
+        self.state.update(cx, |state, _cx| {
+            state.clock = Rc::new(clock);
+        });
+    }
+
+    /// Returns the entity ID associated with this track's state.
+    pub fn entity_id(&self) -> EntityId {
+        self.state.entity_id()
+    }
+
+    /// Resets the track to the one that was provided when the transition was
+    /// created, resting on its last keyframe.
+    ///
+    /// The cache is also cleared.
+    pub fn reset(&self, cx: &mut App) {
+        self.state.update(cx, |state, _cx| {
+            state.started_at = None;
+            state.elapsed_offset = Duration::ZERO;
+            state.keyframes = state.initial_keyframes.clone();
+        });
+        *self.cached_value.borrow_mut() = None;
+    }
+}
+
+#[cfg(all(test, feature = "test-support"))]
+mod tests {
+    use super::*;
+    use crate::{ManualClock, ReducedMotion};
+    use gpui::{AppContext, Point, TestAppContext};
+
+    fn overshoot() -> Keyframes<f32> {
+        Keyframes::new(0.0_f32)
+            .with_keyframe(0.3, 120.0, linear)
+            .with_keyframe(1.0, 100.0, |t| t * t)
+    }
+
+    #[test]
+    fn test_keyframes_at_stops() {
+        let keyframes = overshoot();
+
+        assert_eq!(keyframes.evaluate(0.0), 0.0);
+        assert_eq!(keyframes.evaluate(0.3), 120.0);
+        assert_eq!(keyframes.evaluate(1.0), 100.0);
+    }
+
+    #[test]
+    fn test_keyframes_per_segment_easing() {
+        let keyframes = overshoot();
+
+        // First segment is linear.
+        assert!((keyframes.evaluate(0.15) - 60.0).abs() < 0.001);
+
+        // Second segment is quadratic: halfway through gives a delta of 0.25.
+        assert!((keyframes.evaluate(0.65) - 115.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_keyframes_clamp_time() {
+        let keyframes = overshoot();
+
+        assert_eq!(keyframes.evaluate(-1.0), 0.0);
+        assert_eq!(keyframes.evaluate(2.0), 100.0);
+    }
+
+    #[test]
+    fn test_keyframes_nan() {
+        let keyframes = overshoot().with_keyframe(f32::NAN, 50.0, linear);
+
+        let offsets: Vec<f32> = keyframes.keyframes().iter().map(|k| k.offset).collect();
+        assert_eq!(offsets, vec![0.0, 0.0, 0.3, 1.0]);
+        assert_eq!(keyframes.evaluate(f32::NAN), 50.0);
+    }
+
+    #[test]
+    fn test_keyframes_are_sorted() {
+        let keyframes = Keyframes::new(0.0_f32)
+            .with_keyframe(1.0, 10.0, linear)
+            .with_keyframe(0.5, 50.0, linear);
+
+        let offsets: Vec<f32> = keyframes.keyframes().iter().map(|k| k.offset).collect();
+        assert_eq!(offsets, vec![0.0, 0.5, 1.0]);
+        assert_eq!(keyframes.evaluate(0.5), 50.0);
+    }
+
+    #[test]
+    fn test_keyframes_hold_after_last() {
+        let keyframes = Keyframes::new(0.0_f32).with_keyframe(0.5, 10.0, linear);
+
+        assert_eq!(keyframes.evaluate(0.9), 10.0);
+    }
+
+    #[test]
+    fn test_keyframes_jump_at_same_offset() {
+        let keyframes = Keyframes::new(0.0_f32)
+            .with_keyframe(0.5, 10.0, linear)
+            .with_keyframe(0.5, 20.0, linear)
+            .with_keyframe(1.0, 30.0, linear);
+
+        assert_eq!(keyframes.evaluate(0.5), 20.0);
+        assert_eq!(keyframes.evaluate(0.75), 25.0);
+    }
+
+    #[test]
+    fn test_keyframes_with_point() {
+        let keyframes = Keyframes::new(Point { x: 0.0_f32, y: 0.0 }).with_keyframe(
+            1.0,
+            Point { x: 10.0, y: 20.0 },
+            linear,
+        );
+
+        assert_eq!(keyframes.evaluate(0.5), Point { x: 5.0, y: 10.0 });
+    }
+
+    #[gpui::test]
+    fn test_keyframe_transition_starts_completed(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let state = cx.new(|_| KeyframeState::new(overshoot()));
+            let transition = KeyframeTransition::new(state, Duration::from_millis(100));
+
+            let (in_progress, value) = transition.raw_evaluate(cx);
+            assert!(!in_progress);
+            assert_eq!(value, 100.0);
+        });
+    }
+
+    #[gpui::test]
+    fn test_keyframe_transition_playback(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let clock = ManualClock::new();
+            let state =
+                cx.new(|_| KeyframeState::new(Keyframes::new(0.0)).with_clock(clock.clone()));
+            let transition = KeyframeTransition::new(state, Duration::from_millis(100));
+
+            assert!(transition.update(cx, |keyframes, _cx| *keyframes = overshoot()));
+
+            clock.advance(Duration::from_millis(30));
+            let (in_progress, value) = transition.raw_evaluate(cx);
+            assert!(in_progress);
+            assert!((value - 120.0).abs() < 0.001);
+
+            clock.advance(Duration::from_millis(70));
+            let (in_progress, value) = transition.raw_evaluate(cx);
+            assert!(!in_progress);
+            assert_eq!(value, 100.0);
+
+            // An unchanged track isn't played again, even when it is rebuilt.
+            assert!(!transition.update(cx, |_keyframes, _cx| {}));
+            assert!(!transition.update(cx, |keyframes, _cx| *keyframes = overshoot()));
+            assert!(!transition.raw_evaluate(cx).0);
+        });
+    }
+
+    #[gpui::test]
+    fn test_keyframe_transition_follows_motion_settings(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let clock = ManualClock::new();
+            let state = cx.new(|_| KeyframeState::new(overshoot()).with_clock(clock.clone()));
+            let transition = KeyframeTransition::new(state, Duration::from_millis(100));
+
+            // At half speed, the track is only 30% through after 60ms.
+            MotionSettings::set(MotionSettings::default().with_time_scale(0.5), cx);
+            transition.restart(cx);
+            clock.advance(Duration::from_millis(60));
+            assert!((transition.raw_evaluate(cx).1 - 120.0).abs() < 0.001);
+
+            // Going back to full speed keeps the progress made so far.
+            MotionSettings::set(MotionSettings::default(), cx);
+            transition.raw_evaluate(cx);
+            assert!((transition.evaluate_progress(cx) - 0.3).abs() < 0.001);
+
+            // Reduced motion snaps to the last keyframe, except for essential tracks.
+            MotionSettings::set(
+                MotionSettings::default().with_reduced_motion(Some(ReducedMotion::Snap)),
+                cx,
+            );
+            transition.restart(cx);
+            assert_eq!(transition.raw_evaluate(cx), (false, 100.0));
+
+            let essential = transition.clone().essential(true);
+            assert_eq!(essential.raw_evaluate(cx), (true, 0.0));
+        });
+    }
+
+    #[gpui::test]
+    fn test_keyframe_transition_restart_and_reset(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let clock = ManualClock::new();
+            let state = cx.new(|_| KeyframeState::new(overshoot()).with_clock(clock.clone()));
+            let transition = KeyframeTransition::new(state, Duration::from_millis(100));
+
+            transition.restart(cx);
+            assert_eq!(transition.evaluate_progress(cx), 0.0);
+            assert_eq!(transition.raw_evaluate(cx).1, 0.0);
+
+            transition.update(cx, |keyframes, _cx| {
+                *keyframes = Keyframes::new(5.0);
+            });
+            transition.reset(cx);
+
+            assert_eq!(transition.evaluate_progress(cx), 1.0);
+            assert_eq!(*transition.read_keyframes(cx).last_value(), 100.0);
+        });
+    }
+}
@@ -0,0 +1,260 @@
+//! An element that animates changes to its child's layout.
+
+use std::{panic, rc::Rc, time::Duration};
+
+use gpui::{
+    AnyElement, App, AppContext, Bounds, ContentMask, Element, ElementId, Entity, GlobalElementId,
+    InspectorElementId, IntoElement, LayoutId, Pixels, Window, linear,
+};
+
+use crate::{Transition, TransitionState};
+
+/// Wraps `child` in a [`LayoutTransition`] with the given ID.
+pub fn layout_transition(id: impl Into<ElementId>, child: impl IntoElement) -> LayoutTransition {
+    LayoutTransition {
+        id: id.into(),
+        child: child.into_any_element(),
+        duration: Duration::from_millis(200),
+        easing: Rc::new(linear),
+    }
+}
+
+/// An element that makes its child glide to its new position when its layout
+/// changes, instead of jumping there.
+///
+/// This uses the FLIP technique: the child is laid out at its new bounds straight
+/// away, so the rest of the layout isn't affected, and is then painted offset
+/// towards its previous bounds. The offset is animated away with a
+/// [`Transition<Bounds<Pixels>>`](crate::Transition), which is continuous, so a
+/// layout change in the middle of an animation carries on from where the child is.
+///
+/// GPUI can't scale an element, so size changes are animated by clipping the
+/// child to the animated bounds. A growing child is revealed gradually, while a
+/// shrinking child takes its new size immediately. The child is only clipped
+/// while its size is animating, so at rest its shadows and overflow are painted
+/// as usual.
+///
+/// Changes are detected by comparing the child's bounds in the layout between
+/// frames. Offsets applied by its ancestors, such as scrolling, move the child
+/// straight away and aren't animated.
+///
+/// # Example
+///
+/// ```ignore
+/// div()
+///     .flex()
+///     .children(self.items.iter().map(|item| {
+///         layout_transition(item.id, render_item(item))
+///             .duration(Duration::from_millis(300))
+///             .with_easing(ease_in_out)
+///     }))
+/// ```
+pub struct LayoutTransition {
+    id: ElementId,
+    child: AnyElement,
+    duration: Duration,
+    easing: Rc<dyn Fn(f32) -> f32>,
+}
+
+impl LayoutTransition {
+    /// Sets how long it takes the child to move to its new bounds. Defaults to 200ms.
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Sets the easing function used when moving to new bounds.
+    pub fn with_easing(mut self, easing: impl Fn(f32) -> f32 + 'static) -> Self {
+        self.easing = Rc::new(easing);
+        self
+    }
+}
+
+impl IntoElement for LayoutTransition {
+    type Element = Self;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+impl Element for LayoutTransition {
+    type RequestLayoutState = ();
+    /// The content mask to paint the child in while its size is animating.
+    type PrepaintState = Option<ContentMask<Pixels>>;
+
+    fn id(&self) -> Option<ElementId> {
+        Some(self.id.clone())
+    }
+
+    fn source_location(&self) -> Option<&'static panic::Location<'static>> {
+        None
+    }
+
+    fn request_layout(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _inspector_id: Option<&InspectorElementId>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> (LayoutId, Self::RequestLayoutState) {
+        // Take the child's place in the layout, so that it is laid out as if it
+        // wasn't wrapped.
+        (self.child.request_layout(window, cx), ())
+    }
+
+    fn prepaint(
+        &mut self,
+        id: Option<&GlobalElementId>,
+        _inspector_id: Option<&InspectorElementId>,
+        bounds: Bounds<Pixels>,
+        _request_layout: &mut Self::RequestLayoutState,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Self::PrepaintState {
+        // Leave out the offsets of the ancestors, such as their scroll offsets, so
+        // that only changes to the layout itself are animated.
+        let element_offset = window.element_offset();
+        let layout_bounds = Bounds {
+            origin: bounds.origin - element_offset,
+            size: bounds.size,
+        };
+
+        let state = window.with_element_state(
+            id.unwrap(),
+            |state: Option<Entity<TransitionState<Bounds<Pixels>>>>, _window| {
+                let state =
+                    state.unwrap_or_else(|| cx.new(|_cx| TransitionState::new(layout_bounds)));
+                (state.clone(), state)
+            },
+        );
+
+        let easing = self.easing.clone();
+        let transition =
+            Transition::new(state, self.duration).with_easing(move |delta| easing(delta));
+
+        transition.update(cx, |goal, _cx| *goal = layout_bounds);
+        let animated_bounds = *transition.evaluate(window, cx);
+
+        window.with_element_offset(animated_bounds.origin - layout_bounds.origin, |window| {
+            self.child.prepaint(window, cx);
+        });
+
+        (animated_bounds.size != layout_bounds.size).then(|| ContentMask {
+            bounds: Bounds {
+                origin: animated_bounds.origin + element_offset,
+                size: animated_bounds.size,
+            },
+        })
+    }
+
+    fn paint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _inspector_id: Option<&InspectorElementId>,
+        _bounds: Bounds<Pixels>,
+        _request_layout: &mut Self::RequestLayoutState,
+        mask: &mut Self::PrepaintState,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        window.with_content_mask(mask.clone(), |window| self.child.paint(window, cx));
+    }
+}
+
+#[cfg(all(test, feature = "test-support"))]
+mod tests {
+    use super::*;
+    use gpui::{
+        Context, Render, ScrollHandle, Styled, TestAppContext, VisualTestContext, div, point,
+        prelude::*, px,
+    };
+
+    struct TestView {
+        offset: Pixels,
+        duration: Duration,
+    }
+
+    impl Render for TestView {
+        fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+            div().flex().child(div().w(self.offset).h(px(10.))).child(
+                layout_transition("item", div().debug_selector(|| "item".into()).size(px(10.)))
+                    .duration(self.duration),
+            )
+        }
+    }
+
+    fn set_offset(view: &Entity<TestView>, cx: &mut VisualTestContext, offset: Pixels) {
+        view.update(cx, |view, cx| {
+            view.offset = offset;
+            cx.notify();
+        });
+        cx.run_until_parked();
+    }
+
+    fn item_x(cx: &mut VisualTestContext) -> Pixels {
+        cx.debug_bounds("item").unwrap().origin.x
+    }
+
+    #[gpui::test]
+    fn test_layout_transition_glides_to_new_bounds(cx: &mut TestAppContext) {
+        let (view, cx) = cx.add_window_view(|_window, _cx| TestView {
+            offset: px(0.),
+            duration: Duration::from_secs(60),
+        });
+        cx.run_until_parked();
+        assert_eq!(item_x(cx), px(0.));
+
+        // The item is laid out at its new position, but painted close to its
+        // previous one.
+        set_offset(&view, cx, px(100.));
+        assert!(item_x(cx) < px(1.));
+    }
+
+    #[gpui::test]
+    fn test_layout_transition_without_duration_jumps(cx: &mut TestAppContext) {
+        let (view, cx) = cx.add_window_view(|_window, _cx| TestView {
+            offset: px(0.),
+            duration: Duration::ZERO,
+        });
+        cx.run_until_parked();
+
+        set_offset(&view, cx, px(100.));
+        assert_eq!(item_x(cx), px(100.));
+    }
+
+    struct ScrollView {
+        scroll: ScrollHandle,
+    }
+
+    impl Render for ScrollView {
+        fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+            div()
+                .id("list")
+                .h(px(20.))
+                .overflow_y_scroll()
+                .track_scroll(&self.scroll)
+                .child(div().h(px(50.)))
+                .child(
+                    layout_transition("item", div().debug_selector(|| "item".into()).size(px(10.)))
+                        .duration(Duration::from_secs(60)),
+                )
+        }
+    }
+
+    #[gpui::test]
+    fn test_layout_transition_follows_scrolling(cx: &mut TestAppContext) {
+        let scroll = ScrollHandle::new();
+        let (view, cx) = cx.add_window_view(|_window, _cx| ScrollView {
+            scroll: scroll.clone(),
+        });
+        cx.run_until_parked();
+        assert_eq!(cx.debug_bounds("item").unwrap().origin.y, px(50.));
+
+        // Scrolling moves the item straight away.
+        scroll.set_offset(point(px(0.), px(-30.)));
+        view.update(cx, |_view, cx| cx.notify());
+        cx.run_until_parked();
+        assert_eq!(cx.debug_bounds("item").unwrap().origin.y, px(20.));
+    }
+}
@@ -6,17 +6,17 @@ use std::{
 };
 
 use gpui::{
-    Bounds, Corners, DevicePixels, Edges, Percentage, Pixels, Point, Radians, Rems, Rgba, Size,
-    colors::Colors, px,
+    Bounds, Corners, DevicePixels, Edges, Hsla, Percentage, Pixels, Point, Radians, Rems, Rgba,
+    Size, colors::Colors, px,
 };
 
-use crate::BoolLerp;
+use crate::{BoolLerp, color::lerp_hue};
 
 /// A trait for types that can be linearly interpolated.
 ///
 /// Linear interpolation (lerp) calculates intermediate values between two points.
-/// Given a start value `a`, an end value `b`, and a delta `t` in the range `[0, 1]`,
-/// the interpolated value is calculated as `a + (b - a) * t`.
+/// Given a start value `a`, an end value `b`, and a delta `t`, usually in the range
+/// `[0, 1]`, the interpolated value is calculated as `a + (b - a) * t`.
 ///
 /// # Implementations
 ///
@@ -24,9 +24,24 @@ use crate::BoolLerp;
 /// - Floating-point types: `f32`, `f64`
 /// - Integer types: `u8`, `u16`, `u32`, `u64`, `u128`, `usize`, `i8`, `i16`, `i32`, `i64`, `i128`, `isize`
 /// - GPUI geometry types: [`Point`], [`Size`], [`Edges`], [`Corners`], [`Bounds`]
-/// - GPUI color types: [`Rgba`], [`Colors`]
+/// - GPUI color types: [`Rgba`], [`Hsla`], [`Colors`]
 /// - GPUI unit types: [`Pixels`], [`Rems`], [`DevicePixels`], [`Percentage`], [`Radians`]
 /// - [`BoolLerp`] for animating boolean-like values
+/// - [`LinearRgba`](crate::LinearRgba), [`Oklab`](crate::Oklab) and [`Oklch`](crate::Oklch)
+///   for interpolating colors in other color spaces
+///
+/// [`Rgba`] interpolates the gamma-encoded sRGB channels directly, which is cheap
+/// but can produce dull midpoints. [`Hsla`] takes the shortest way around the
+/// color wheel.
+///
+/// # Overshoot
+///
+/// Easing functions such as [`ease_out_back`](crate::easing::ease_out_back) can
+/// produce a delta below 0 or above 1. Numbers, units and geometry extrapolate
+/// past their endpoints, while types that can't leave their range saturate
+/// instead: integers are clamped to their bounds (so unsigned integers never go
+/// below 0), color channels and alpha are clamped between 0 and 1, and
+/// [`BoolLerp`] stays between its two states.
 ///
 /// # Example
 ///
@@ -46,10 +61,12 @@ pub trait Lerp {
     /// # Arguments
     ///
     /// * `to` - The target value to interpolate towards.
-    /// * `delta` - A value between 0.0 and 1.0 representing the interpolation progress.
+    /// * `delta` - A value representing the interpolation progress, usually between
+    ///   0.0 and 1.0.
     ///   - `0.0` returns a value equal to `self`
     ///   - `1.0` returns a value equal to `to`
     ///   - Values in between return proportionally interpolated values
+    ///   - Values outside of 0.0 to 1.0 extrapolate or saturate (see [Overshoot](#overshoot))
     ///
     /// # Returns
     ///
@@ -76,6 +93,8 @@ macro_rules! int_lerps {
         $(
             impl Lerp for $ty {
                 fn lerp(&self, to: &Self, delta: f32) -> Self {
+                    // Casting from a float saturates, so overshooting past the
+                    // integer's bounds clamps to them.
                     lerp(*self as $ty_into, *to as $ty_into, delta as $ty_into) as $ty
                 }
             }
@@ -120,7 +139,6 @@ struct_lerps!(
     Edges<T> { top, right, bottom, left },
     Corners<T> { top_left, top_right, bottom_right, bottom_left },
     Bounds<T> { origin, size },
-    Rgba { r, g, b, a },
     Colors { text, selected_text, background, disabled, selected, border, separator, container }
 );
 
@@ -150,6 +168,38 @@ impl<N: Lerp + Copy + PartialOrd + From<u8>> Lerp for BoolLerp<N> {
     }
 }
 
+impl Lerp for Rgba {
+    fn lerp(&self, to: &Self, delta: f32) -> Self {
+        // Channels outside of 0 to 1 aren't valid colors, so they are clamped
+        // rather than extrapolated.
+        Rgba {
+            r: self.r.lerp(&to.r, delta).clamp(0., 1.),
+            g: self.g.lerp(&to.g, delta).clamp(0., 1.),
+            b: self.b.lerp(&to.b, delta).clamp(0., 1.),
+            a: self.a.lerp(&to.a, delta).clamp(0., 1.),
+        }
+    }
+}
+
+impl Lerp for Hsla {
+    fn lerp(&self, to: &Self, delta: f32) -> Self {
+        // A color without saturation (i.e. a grey) has no meaningful hue, so it
+        // takes the hue of the other color.
+        let (from_h, to_h) = match (self.s == 0., to.s == 0.) {
+            (true, false) => (to.h, to.h),
+            (false, true) => (self.h, self.h),
+            _ => (self.h, to.h),
+        };
+
+        Hsla {
+            h: lerp_hue(from_h, to_h, delta),
+            s: self.s.lerp(&to.s, delta).clamp(0., 1.),
+            l: self.l.lerp(&to.l, delta).clamp(0., 1.),
+            a: self.a.lerp(&to.a, delta).clamp(0., 1.),
+        }
+    }
+}
+
 fn lerp<T>(a: T, b: T, t: T) -> T
 where
     T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
@@ -221,6 +271,58 @@ mod tests {
         assert_eq!((-100_isize).lerp(&100_isize, 0.5), 0);
     }
 
+    #[test]
+    fn test_lerp_overshoot() {
+        // Floats and signed integers extrapolate.
+        assert_eq!(0.0_f32.lerp(&100.0, 1.1), 110.0);
+        assert_eq!(0.0_f32.lerp(&100.0, -0.1), -10.0);
+        assert_eq!(0_i32.lerp(&100, -0.1), -10);
+
+        // Unsigned integers saturate.
+        assert_eq!(10_u8.lerp(&100, -0.5), 0);
+        assert_eq!(100_u8.lerp(&250, 1.5), 255);
+        assert_eq!(10_u32.lerp(&100, -0.5), 0);
+
+        // Color channels and alpha are clamped.
+        let from = Rgba {
+            r: 0.2,
+            g: 0.5,
+            b: 0.8,
+            a: 0.5,
+        };
+        let to = Rgba {
+            r: 0.8,
+            g: 0.5,
+            b: 0.2,
+            a: 1.0,
+        };
+        assert_eq!(
+            from.lerp(&to, 2.0),
+            Rgba {
+                r: 1.0,
+                g: 0.5,
+                b: 0.0,
+                a: 1.0
+            }
+        );
+
+        let from = Hsla {
+            h: 0.0,
+            s: 0.5,
+            l: 0.5,
+            a: 0.5,
+        };
+        let to = Hsla {
+            h: 0.1,
+            s: 1.0,
+            l: 0.9,
+            a: 1.0,
+        };
+        let overshot = from.lerp(&to, 2.0);
+        assert!((overshot.h - 0.2).abs() < 0.0001);
+        assert_eq!((overshot.s, overshot.l, overshot.a), (1.0, 1.0, 1.0));
+    }
+
     #[test]
     fn test_point_lerp() {
         let start: Point<f32> = Point { x: 0.0, y: 0.0 };
@@ -367,6 +469,46 @@ mod tests {
         assert_eq!(mid.a, 0.5);
     }
 
+    #[test]
+    fn test_hsla_lerp_takes_shortest_hue() {
+        let start = Hsla {
+            h: 0.9,
+            s: 1.0,
+            l: 0.5,
+            a: 1.0,
+        };
+        let end = Hsla {
+            h: 0.3,
+            s: 0.5,
+            l: 0.7,
+            a: 0.0,
+        };
+
+        let mid = start.lerp(&end, 0.5);
+        assert!((mid.h - 0.1).abs() < 0.0001);
+        assert_eq!(mid.s, 0.75);
+        assert!((mid.l - 0.6).abs() < 0.0001);
+        assert_eq!(mid.a, 0.5);
+    }
+
+    #[test]
+    fn test_hsla_lerp_from_grey_keeps_hue() {
+        let grey = Hsla {
+            h: 0.0,
+            s: 0.0,
+            l: 0.5,
+            a: 1.0,
+        };
+        let blue = Hsla {
+            h: 0.66,
+            s: 1.0,
+            l: 0.5,
+            a: 1.0,
+        };
+
+        assert_eq!(grey.lerp(&blue, 0.5).h, 0.66);
+    }
+
     #[test]
     fn test_pixels_lerp() {
         let start = px(0.0);
@@ -14,6 +14,46 @@
 //!
 //! - [`TransitionState`] - Internal state container used by [`Transition`].
 //!
+//! - [`SpringTransition`] - A physics-based alternative to [`Transition`] that
+//!   animates toward its goal with a damped spring, carrying its velocity over
+//!   when the goal changes. Values are animated through the [`Components`] trait.
+//!
+//! - [`Oklab`], [`Oklch`] and [`LinearRgba`] - Wrappers around [`gpui::Rgba`]
+//!   that interpolate in perceptual or linear color spaces instead of raw sRGB.
+//!
+//! - [`Keyframes`] - A track of values at normalized offsets with per-segment
+//!   easing, played over time by a [`KeyframeTransition`].
+//!
+//! - [`TransitionGroup`] - A set of keyed transitions that share a goal and
+//!   start one after another, for staggering the items of a list.
+//!
+//! - [`GestureTransition`] - A progress driven directly by a drag, which carries
+//!   the drag's velocity into a spring or timed motion to a snap point on release.
+//!
+//! - [`Timeline`] - Choreographs several [`Tween`]s in sequence and in parallel
+//!   on a single playhead that can be played, reversed and seeked.
+//!
+//! - [`Presence`] - An element that animates keyed children in and out, keeping
+//!   removed children mounted until their exit transition has finished.
+//!
+//! - [`LayoutTransition`] - An element that makes its child glide to its new
+//!   bounds when its layout changes.
+//!
+//! - [`SharedElement`] - An element that morphs from the last element rendered
+//!   with the same tag, for transitions between views.
+//!
+//! - [`StyledTransition`] - Extension methods for [`gpui::Styled`] elements that
+//!   transition style properties, such as `transition_bg` and `transition_opacity`.
+//!
+//! - [`easing`] - The Penner easing functions, combinators that compose them,
+//!   and a parser for CSS easing strings such as `cubic-bezier(0.2, 0, 0, 1)`.
+//!
+//! - [`MotionSettings`] - An app-wide global that scales the speed of every
+//!   transition and snaps or shortens them when motion should be reduced.
+//!
+//! - [`Clock`] - The source of time a transition is evaluated against. Swap in a
+//!   [`ManualClock`] to step transitions deterministically.
+//!
 //! - [`BoolLerp`] - A wrapper type for animating boolean-like values with smooth
 //!   intermediate states.
 //!
@@ -47,8 +87,9 @@
 use std::{
     borrow::BorrowMut,
     cell::{Ref, RefCell},
+    collections::VecDeque,
     rc::Rc,
-    time::{Duration, Instant},
+    time::Duration,
 };
 
 use gpui::{App, Entity, EntityId, Window, linear, prelude::*};
@@ -56,12 +97,81 @@ use gpui::{App, Entity, EntityId, Window, linear, prelude::*};
 mod lerp;
 pub use lerp::Lerp;
 
+/// Derives [`Lerp`] for a struct or enum.
+///
+/// Requires the `derive` feature. Fields are interpolated with their own
+/// [`Lerp`] implementations, unless marked with `#[lerp(skip)]` (always take the
+/// target's value) or `#[lerp(snap)]` (jump to the target's value halfway through).
+///
+/// ```ignore
+/// #[derive(Clone, PartialEq, Lerp)]
+/// struct CardStyle {
+///     background: Rgba,
+///     padding: Edges<Pixels>,
+///     #[lerp(snap)]
+///     cursor: CursorStyle,
+/// }
+/// ```
+#[cfg(feature = "derive")]
+pub use gpui_transitions_derive::Lerp;
+
 mod window;
 pub use window::WindowUseTransition;
 
 mod state;
 pub use state::TransitionState;
 
+mod clock;
+pub use clock::*;
+
+mod color;
+pub use color::{LinearRgba, Oklab, Oklch};
+
+mod components;
+pub use components::Components;
+
+mod spring;
+pub use spring::{SpringState, SpringTransition};
+
+mod keyframes;
+pub use keyframes::{Keyframe, KeyframeState, KeyframeTransition, Keyframes};
+
+mod group;
+pub use group::{TransitionGroup, TransitionGroupState};
+
+mod gesture;
+pub use gesture::{GestureRelease, GestureState, GestureTransition};
+
+mod timeline;
+pub use timeline::{Timeline, TimelineState, Track, Tween};
+
+mod presence;
+pub use presence::{Presence, PresenceChild, PresenceRender, PresenceTransition, presence};
+
+mod layout;
+pub use layout::{LayoutTransition, layout_transition};
+
+mod shared;
+pub use shared::{SharedElement, SharedElementRegistry, SharedElementSnapshot, shared_element};
+
+mod styled;
+pub use styled::{StyleTransition, StyledTransition};
+
+pub mod easing;
+
+mod playback;
+pub use playback::{InterruptPolicy, Repeat, ReversalPolicy};
+
+mod motion;
+pub use motion::{MotionSettings, ReducedMotion};
+
+mod scaling;
+pub use scaling::Distance;
+use scaling::{DurationScaling, ScaleBy};
+
+mod finished;
+pub use finished::{TransitionFinished, TransitionOutcome};
+
 mod bool_lerp;
 pub use bool_lerp::*;
 
@@ -104,6 +214,9 @@ pub struct Transition<T: Lerp + Clone + PartialEq + 'static> {
     /// The amount of time for which this transtion should run.
     duration_secs: f32,
 
+    /// The amount of time to wait after the goal changes before the transition starts.
+    delay: Duration,
+
     /// A function that takes a delta between 0 and 1 and returns a new delta
     /// between 0 and 1 based on the given easing function.
     easing: Rc<dyn Fn(f32) -> f32>,
@@ -117,17 +230,59 @@ pub struct Transition<T: Lerp + Clone + PartialEq + 'static> {
     /// If true, transitions smoothly from current animated value to new goal.
     /// If false, restarts from the original start value.
     continuous: bool,
+
+    /// How many times the transition plays before it finishes.
+    repeat: Repeat,
+
+    /// Whether every other repetition plays backwards, from the goal to the start.
+    alternate: bool,
+
+    /// What to do when the goal is set back to the start value mid-animation.
+    reversal: ReversalPolicy,
+
+    /// What to do when the goal changes mid-animation.
+    interrupt: InterruptPolicy,
+
+    /// How the duration of an animation that interrupts another one is scaled,
+    /// if at all.
+    duration_scaling: Option<DurationScaling<T>>,
+
+    /// Whether the transition ignores the reduced motion of [`MotionSettings`].
+    essential: bool,
+
+    /// Called with the new goal whenever the goal changes.
+    on_start: Option<TransitionCallback<T>>,
+
+    /// Called with the goal once the transition has reached it.
+    on_complete: Option<TransitionCallback<T>>,
+
+    /// Called with the previous goal when it is replaced before being reached.
+    on_interrupted: Option<TransitionCallback<T>>,
 }
 
+/// A callback registered on a [`Transition`], which receives the goal the
+/// event relates to.
+pub type TransitionCallback<T> = Rc<dyn Fn(&T, &mut App)>;
+
 impl<T: Lerp + Clone + PartialEq + 'static> Transition<T> {
     /// Create a new transition with the given duration using the specified state.
     pub fn new(state: Entity<TransitionState<T>>, duration: Duration) -> Self {
         Self {
             duration_secs: duration.as_secs_f32(),
+            delay: Duration::ZERO,
             easing: Rc::new(linear),
             state,
             cached_value: RefCell::new(None),
             continuous: true,
+            repeat: Repeat::default(),
+            alternate: false,
+            reversal: ReversalPolicy::default(),
+            interrupt: InterruptPolicy::default(),
+            duration_scaling: None,
+            essential: false,
+            on_start: None,
+            on_complete: None,
+            on_interrupted: None,
         }
     }
 
@@ -139,6 +294,16 @@ impl<T: Lerp + Clone + PartialEq + 'static> Transition<T> {
         self
     }
 
+    /// Sets how long to wait after the goal changes before the transition starts.
+    ///
+    /// During the delay the transition stays at its start value, but is still
+    /// considered in progress, so [`evaluate`](Self::evaluate) keeps requesting
+    /// animation frames until it starts moving.
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
     /// Sets whether the transition should be continuous.
     ///
     /// On goal updates, transitions continue from the current value by default.
@@ -148,33 +313,262 @@ impl<T: Lerp + Clone + PartialEq + 'static> Transition<T> {
         self
     }
 
-    fn default_goal_updated_at(&self) -> Instant {
-        Instant::now() - Duration::from_secs_f32(self.duration_secs)
+    /// Sets how many times the transition plays each time its goal changes.
+    ///
+    /// Every repetition animates from the start value to the goal, unless
+    /// [`alternate`](Self::alternate) is set. While repeating, the transition is
+    /// in progress, so [`evaluate`](Self::evaluate) keeps requesting animation
+    /// frames. The delay set with [`with_delay`](Self::with_delay) only applies
+    /// before the first repetition.
+    pub fn repeat(mut self, repeat: Repeat) -> Self {
+        self.repeat = repeat;
+        self
+    }
+
+    /// Sets whether every other repetition plays backwards, like CSS's
+    /// `animation-direction: alternate`.
+    ///
+    /// With an even number of repetitions, the transition ends back at its start
+    /// value. It is still considered complete once it finishes.
+    pub fn alternate(mut self, alternate: bool) -> Self {
+        self.alternate = alternate;
+        self
+    }
+
+    /// Sets what the transition does when its goal changes before the previous
+    /// goal has been reached.
+    ///
+    /// By default the new goal replaces the previous one straight away. See
+    /// [`InterruptPolicy`] for the alternatives, such as queueing goals so that
+    /// each of them is reached in turn. A [`reversal`](Self::reversal) policy or
+    /// [duration scaling](Self::scale_duration_by_progress) only applies to goals
+    /// that replace the previous one.
+    pub fn interrupt(mut self, interrupt: InterruptPolicy) -> Self {
+        self.interrupt = interrupt;
+        self
+    }
+
+    /// Sets what the transition does when its goal is set back to the value it
+    /// started from before reaching its goal, such as when the pointer leaves an
+    /// element that is still animating its hover.
+    ///
+    /// By default the transition eases towards the new goal from its current
+    /// value, so an ease-out animation eases out again on the way back.
+    /// [`ReversalPolicy::Retrace`] plays the animation backwards instead, as
+    /// [`reverse`](Self::reverse) does.
+    pub fn reversal(mut self, reversal: ReversalPolicy) -> Self {
+        self.reversal = reversal;
+        self
+    }
+
+    /// Scales the duration of an animation that interrupts another one by the
+    /// progress the interrupted animation had left, clamped between `min` and
+    /// `max`.
+    ///
+    /// By default, every goal change animates over the full duration, so a
+    /// transition retargeted when it was 90% done takes as long to cover the
+    /// last stretch as it took to cover the whole distance. With this set, it
+    /// takes 10% of the duration instead. The scale always applies to the
+    /// configured duration, so a transition that is retargeted many times in a
+    /// row doesn't keep getting faster. This suits
+    /// goals that move by small amounts, such as a thumb following a value. Goal
+    /// changes after an animation has finished always use the full duration.
+    pub fn scale_duration_by_progress(mut self, min: Duration, max: Duration) -> Self {
+        self.duration_scaling = Some(DurationScaling {
+            scale_by: ScaleBy::RemainingProgress,
+            min_secs: min.as_secs_f32(),
+            max_secs: max.as_secs_f32(),
+        });
+        self
+    }
+
+    /// Scales the duration of the interrupted animation by the distance to the
+    /// new goal, relative to the distance the interrupted animation was covering,
+    /// clamped between `min` and `max`.
+    ///
+    /// This keeps the speed of the transition roughly constant when it is
+    /// retargeted, wherever the new goal is. Distances are measured with the
+    /// [`Distance`] trait. Goal changes after an animation has finished always
+    /// use the full duration.
+    pub fn scale_duration_by_distance(mut self, min: Duration, max: Duration) -> Self
+    where
+        T: Distance,
+    {
+        self.duration_scaling = Some(DurationScaling {
+            scale_by: ScaleBy::Distance(Rc::new(T::distance)),
+            min_secs: min.as_secs_f32(),
+            max_secs: max.as_secs_f32(),
+        });
+        self
+    }
+
+    /// Sets whether the transition is essential, such as a progress indicator
+    /// that conveys information, and should play even when the app-wide
+    /// [`MotionSettings`] reduce motion. The time scale still applies.
+    pub fn essential(mut self, essential: bool) -> Self {
+        self.essential = essential;
+        self
+    }
+
+    /// Registers a callback that is called whenever the goal changes and a new
+    /// animation begins. It receives the new goal.
+    pub fn on_start(mut self, callback: impl Fn(&T, &mut App) + 'static) -> Self {
+        self.on_start = Some(Rc::new(callback));
+        self
+    }
+
+    /// Registers a callback that is called once the transition reaches its goal.
+    /// It receives the goal that was reached.
+    ///
+    /// Completion is detected when the transition is evaluated, and the callback
+    /// is deferred until the current update cycle has finished. It is called at
+    /// most once per goal change.
+    pub fn on_complete(mut self, callback: impl Fn(&T, &mut App) + 'static) -> Self {
+        self.on_complete = Some(Rc::new(callback));
+        self
+    }
+
+    /// Registers a callback that is called when the goal changes before the
+    /// previous goal was reached. It receives the goal that was abandoned.
+    ///
+    /// For any goal change, either this or [`on_complete`](Self::on_complete) is
+    /// called, never both. This includes [`reset`](Self::reset), which also
+    /// calls this for every queued goal.
+    pub fn on_interrupted(mut self, callback: impl Fn(&T, &mut App) + 'static) -> Self {
+        self.on_interrupted = Some(Rc::new(callback));
+        self
+    }
+
+    /// Returns the duration (in seconds) and delay that the transition plays
+    /// with, after applying the app-wide reduced motion.
+    fn timing(&self, state: &TransitionState<T>, motion: &MotionSettings) -> (f32, Duration) {
+        let duration_secs = state.scaled_duration_secs.unwrap_or(self.duration_secs);
+        motion.apply(duration_secs, self.delay, self.essential)
+    }
+
+    /// Returns the linear progress (between 0 and 1) of the current repetition of
+    /// the transition, before easing, and whether the transition has finished.
+    fn progress(&self, state: &TransitionState<T>, motion: &MotionSettings) -> (f32, bool) {
+        let Some(elapsed) = state.elapsed() else {
+            return (1., true);
+        };
+
+        self.progress_at(state, elapsed, motion)
+    }
+
+    /// Returns the linear progress of the transition after the given amount of
+    /// time has elapsed since the goal changed, and whether it has finished.
+    fn progress_at(
+        &self,
+        state: &TransitionState<T>,
+        elapsed: Duration,
+        motion: &MotionSettings,
+    ) -> (f32, bool) {
+        let (duration_secs, delay) = self.timing(state, motion);
+
+        let Some(elapsed) = elapsed.checked_sub(delay) else {
+            return (0., false);
+        };
+
+        let elapsed_iterations = if duration_secs > 0. {
+            elapsed.as_secs_f32() / duration_secs
+        } else {
+            f32::INFINITY
+        };
+
+        // A reversed transition plays the repetition it was reversed in back once.
+        if state.reversed {
+            playback::iteration_progress(elapsed_iterations, Repeat::default(), false)
+        } else {
+            playback::iteration_progress(elapsed_iterations, self.repeat, self.alternate)
+        }
+    }
+
+    /// Applies the easing function to the given progress. A reversed transition
+    /// retraces the eased curve it was reversed on, from its new start (the
+    /// previous goal) back to its new goal (the previous start).
+    fn ease(&self, state: &TransitionState<T>, progress: f32) -> f32 {
+        if state.reversed {
+            1. - (self.easing)(1. - progress)
+        } else {
+            (self.easing)(progress)
+        }
+    }
+
+    /// Swaps the start and end goals of the state, so that the animation plays
+    /// backwards from its current point along the same eased path.
+    fn retrace(&self, state: &mut TransitionState<T>, motion: &MotionSettings) {
+        let (progress, _) = self.progress(state, motion);
+        let (duration_secs, delay) = self.timing(state, motion);
+
+        // Whatever has been played so far is left to play, without a delay.
+        let elapsed = Duration::try_from_secs_f32(duration_secs * (1. - progress))
+            .map_or(Duration::MAX, |elapsed| delay.saturating_add(elapsed));
+
+        std::mem::swap(&mut state.start_goal, &mut state.end_goal);
+        state.last_delta = 1. - state.last_delta;
+        state.reversed = !state.reversed;
+        state.elapsed_offset = elapsed;
+        state.goal_last_updated_at = Some(state.clock.now());
     }
 
     /// Evaluates the value of the transition without using the cache.
-    /// Returns if the transition is finished (bool) and the evaluated value (T).
+    /// Returns if the transition is in progress and not paused (bool) and the
+    /// evaluated value (T).
     fn raw_evaluate(&self, cx: &mut App) -> (bool, T) {
+        let motion = MotionSettings::get(cx);
         let mut state_entity = self.state.as_mut(cx);
         let state: &mut TransitionState<T> = state_entity.borrow_mut();
+        state.set_time_scale(motion.time_scale());
 
-        let elapsed_secs = state
-            .goal_last_updated_at
-            .unwrap_or_else(|| self.default_goal_updated_at())
-            .elapsed()
-            .as_secs_f32();
-        let delta = (self.easing)((elapsed_secs / self.duration_secs).min(1.));
-
-        debug_assert!(
-            (0.0..=1.0).contains(&delta),
-            "delta should always be between 0 and 1"
-        );
+        let (progress, finished) = self.progress(state, &motion);
+        // Overshooting easing functions can return a delta outside of 0 to 1,
+        // which `Lerp` implementations extrapolate or clamp.
+        let delta = self.ease(state, progress);
 
         state.last_delta = delta;
 
         let evaluated_value = state.start_goal.lerp(&state.end_goal, delta);
 
-        (delta != 1., evaluated_value)
+        let completed_goal = (finished && state.completion_pending).then(|| {
+            state.completion_pending = false;
+            state.completed_generation = Some(state.generation);
+            state.end_goal.clone()
+        });
+
+        // Once the goal is reached, the next queued goal starts from it.
+        let started_goal = finished
+            .then(|| state.queued_goals.pop_front())
+            .flatten()
+            .map(|goal| {
+                let last_end_goal = std::mem::replace(&mut state.end_goal, goal);
+                self.start(state, &last_end_goal, progress, true);
+                state.completion_pending = true;
+                state.generation += 1;
+                state.end_goal.clone()
+            });
+
+        // A paused or frozen transition doesn't move, so it doesn't need animation
+        // frames until it is resumed or the time scale changes.
+        let in_progress =
+            (!finished || started_goal.is_some()) && !state.paused && state.time_scale > 0.;
+
+        drop(state_entity);
+
+        if completed_goal.is_some() || started_goal.is_some() {
+            // Wake up anything waiting on the goal to be reached.
+            cx.notify(self.state.entity_id());
+        }
+
+        if let Some((on_complete, goal)) = self.on_complete.clone().zip(completed_goal) {
+            cx.defer(move |cx| on_complete(&goal, cx));
+        }
+
+        if let Some((on_start, goal)) = self.on_start.clone().zip(started_goal) {
+            cx.defer(move |cx| on_start(&goal, cx));
+        }
+
+        (in_progress, evaluated_value)
     }
 
     /// Evaluates and returns the current interpolated value of the transition.
@@ -188,23 +582,48 @@ impl<T: Lerp + Clone + PartialEq + 'static> Transition<T> {
     /// redundant calculations when called multiple times.
     pub fn evaluate(&self, window: &mut Window, cx: &mut App) -> Ref<'_, T> {
         if self.cached_value.borrow().is_none() {
-            let (in_progress, evaluated_value) = self.raw_evaluate(cx);
-
-            if in_progress {
-                window.request_animation_frame();
-            }
-
+            let (_, evaluated_value) = self.evaluate_uncached(window, cx);
             *self.cached_value.borrow_mut() = Some(evaluated_value);
         }
 
         Ref::map(self.cached_value.borrow(), |opt| opt.as_ref().unwrap())
     }
 
+    /// Evaluates the value of the transition for the current frame without using
+    /// the cache, requesting an animation frame if it is still in progress.
+    /// Returns if the transition is in progress (bool) and the evaluated value (T),
+    /// both read at the same time.
+    pub(crate) fn evaluate_uncached(&self, window: &mut Window, cx: &mut App) -> (bool, T) {
+        self.state.read(cx).clock.begin_frame(window);
+
+        let (in_progress, evaluated_value) = self.raw_evaluate(cx);
+
+        if in_progress {
+            window.request_animation_frame();
+        }
+
+        (in_progress, evaluated_value)
+    }
+
+    /// Returns whether the transition has finished playing towards its goal.
+    #[cfg(all(test, feature = "test-support"))]
+    pub(crate) fn is_finished(&self, cx: &App) -> bool {
+        let state = self.state.read(cx);
+        let (_, finished) = self.progress(state, &MotionSettings::get(cx));
+        finished && state.queued_goals.is_empty()
+    }
+
     /// Reads the end goal of the transitions.
     pub fn read_goal<'b>(&'b self, cx: &'b mut App) -> &'b T {
         &self.state.read(cx).end_goal
     }
 
+    /// Reads the goals waiting to be animated to once the current goal is
+    /// reached, when queued by [`InterruptPolicy::Queue`].
+    pub fn read_queue<'b>(&'b self, cx: &'b App) -> &'b VecDeque<T> {
+        &self.state.read(cx).queued_goals
+    }
+
     /// Reads the current value of the cached transition, if it exists.
     pub fn read_cache(&self) -> Ref<'_, Option<T>> {
         self.cached_value.borrow()
@@ -216,14 +635,116 @@ impl<T: Lerp + Clone + PartialEq + 'static> Transition<T> {
     /// has progressed, after applying the easing function. A value of 0.0 means
     /// the transition just started, and 1.0 means it has completed.
     pub fn evaluate_delta<'b>(&'b self, cx: &'b App) -> f32 {
-        let goal_last_updated_at = self
-            .state
-            .read(cx)
-            .goal_last_updated_at
-            .unwrap_or_else(|| self.default_goal_updated_at());
+        let state = self.state.read(cx);
+        let (progress, _) = self.progress(state, &MotionSettings::get(cx));
+        self.ease(state, progress)
+    }
+
+    /// Returns the first and second derivatives of the eased delta with respect
+    /// to time, in deltas per second and deltas per second squared, using
+    /// central differences around the current time, or forward differences at
+    /// the very start of an animation.
+    fn delta_derivatives(&self, state: &TransitionState<T>, motion: &MotionSettings) -> (f32, f32) {
+        let Some(elapsed) = state.elapsed() else {
+            return (0., 0.);
+        };
+
+        let (_, finished) = self.progress_at(state, elapsed, motion);
+        let (duration_secs, _) = self.timing(state, motion);
+
+        // A step of a hundredth of the duration keeps the second difference well
+        // above the precision of `f32`.
+        let step = match Duration::try_from_secs_f32(duration_secs / 100.) {
+            Ok(step) if !finished && !state.paused && !step.is_zero() => step,
+            _ => return (0., 0.),
+        };
+
+        let delta_at = |elapsed| {
+            let (progress, _) = self.progress_at(state, elapsed, motion);
+            self.ease(state, progress)
+        };
+
+        let step_secs = step.as_secs_f32();
+        let current = delta_at(elapsed);
+        let after = delta_at(elapsed + step);
+
+        let (velocity, acceleration) = if elapsed >= step {
+            let before = delta_at(elapsed - step);
+            (
+                (after - before) / (2. * step_secs),
+                (after - 2. * current + before) / (step_secs * step_secs),
+            )
+        } else {
+            // There is no room for a step back, so both steps go forward.
+            let after_next = delta_at(elapsed + 2 * step);
+            (
+                (-3. * current + 4. * after - after_next) / (2. * step_secs),
+                (current - 2. * after + after_next) / (step_secs * step_secs),
+            )
+        };
+
+        // The transition plays slower or faster than real time.
+        let time_scale = state.time_scale;
+        let (velocity, acceleration) = (
+            velocity * time_scale,
+            acceleration * time_scale * time_scale,
+        );
+
+        (velocity, acceleration)
+    }
+
+    /// Scales the difference between the components of the start and end goals
+    /// by `factor`.
+    fn scaled_difference(&self, state: &TransitionState<T>, factor: f32) -> Vec<f32>
+    where
+        T: Components,
+    {
+        state
+            .end_goal
+            .to_components()
+            .into_iter()
+            .zip(state.start_goal.to_components())
+            .map(|(end, start)| (end - start) * factor)
+            .collect()
+    }
 
-        let elapsed_secs = goal_last_updated_at.elapsed().as_secs_f32();
-        (self.easing)((elapsed_secs / self.duration_secs).min(1.))
+    /// Evaluates and returns how fast each [component](Components) of the value
+    /// is changing, in units per second, in the order they are written by
+    /// [`Components::to_components`].
+    ///
+    /// The velocity is the derivative of the easing function, found numerically,
+    /// multiplied by the difference between the components of the start and end
+    /// values of the current animation. The rates are returned as they are,
+    /// rather than as a `T`, because they can be negative or out of the range of
+    /// the type, such as for colors and unsigned integers. They are exact for
+    /// types that interpolate their components linearly, which includes floats,
+    /// GPUI geometry types and the color wrappers. They can be used to hand the
+    /// motion over to a [`SpringTransition`] or to drive effects such as motion
+    /// blur.
+    ///
+    /// A transition that is finished, paused or waiting out its delay has a
+    /// velocity of zero.
+    pub fn evaluate_velocity(&self, cx: &App) -> Vec<f32>
+    where
+        T: Components,
+    {
+        let state = self.state.read(cx);
+        let (velocity, _) = self.delta_derivatives(state, &MotionSettings::get(cx));
+        self.scaled_difference(state, velocity)
+    }
+
+    /// Evaluates and returns how fast the velocity of each component of the
+    /// value is changing, in units per second squared.
+    ///
+    /// Like [`evaluate_velocity`](Self::evaluate_velocity), this is found by
+    /// differentiating the easing function numerically.
+    pub fn evaluate_acceleration(&self, cx: &App) -> Vec<f32>
+    where
+        T: Components,
+    {
+        let state = self.state.read(cx);
+        let (_, acceleration) = self.delta_derivatives(state, &MotionSettings::get(cx));
+        self.scaled_difference(state, acceleration)
     }
 
     /// Updates the goal value for the transition.
@@ -234,7 +755,13 @@ impl<T: Lerp + Clone + PartialEq + 'static> Transition<T> {
     /// new goal.
     ///
     /// Returns `true` if the goal was actually updated (i.e., the new value differs
-    /// from the previous goal), `false` otherwise.
+    /// from the previous goal), `false` otherwise. While the transition is
+    /// animating, the [`interrupt`](Self::interrupt) policy may ignore the new
+    /// goal, or queue it, in which case this returns `true`.
+    ///
+    /// When the goal is updated, the [`on_start`](Self::on_start) callback is
+    /// called. If the previous goal had not been reached yet,
+    /// [`on_interrupted`](Self::on_interrupted) is called for it first.
     ///
     /// Note: This method does not automatically notify GPUI of changes. You should
     /// call `cx.notify()` within the closure if you want to trigger a re-render.
@@ -243,10 +770,17 @@ impl<T: Lerp + Clone + PartialEq + 'static> Transition<T> {
         cx: &mut App,
         update: impl FnOnce(&mut T, &mut crate::Context<TransitionState<T>>) -> R,
     ) -> bool {
-        let mut was_updated = false;
+        let mut previous_goal = None;
+        let mut new_goal = None;
+        let mut was_queued = false;
+
+        let motion = MotionSettings::get(cx);
 
         self.state.update(cx, |state, cx| {
+            state.set_time_scale(motion.time_scale());
+
             let last_end_goal = state.end_goal.clone();
+            let (progress, mut was_finished) = self.progress(state, &motion);
 
             update(&mut state.end_goal, cx);
 
@@ -254,18 +788,300 @@ impl<T: Lerp + Clone + PartialEq + 'static> Transition<T> {
                 return;
             };
 
-            state.goal_last_updated_at = Some(Instant::now());
+            // Goals queued behind the current one are still waiting their turn.
+            let interrupting = !was_finished || !state.queued_goals.is_empty();
+
+            match self.interrupt {
+                InterruptPolicy::Replace => {}
+                _ if !interrupting => {}
+                InterruptPolicy::Ignore => {
+                    state.end_goal = last_end_goal;
+                    return;
+                }
+                InterruptPolicy::Queue(capacity) => {
+                    let goal = std::mem::replace(&mut state.end_goal, last_end_goal);
+                    let last_goal = state.queued_goals.back().unwrap_or(&state.end_goal);
+
+                    if capacity > 0 && goal != *last_goal {
+                        if state.queued_goals.len() >= capacity {
+                            state.queued_goals.pop_front();
+                        }
+                        state.queued_goals.push_back(goal);
+                        was_queued = true;
+                    }
+                    return;
+                }
+                InterruptPolicy::JumpToEnd => {
+                    state.last_delta = 1.;
+                    state.completed_generation = Some(state.generation);
+                    was_finished = true;
+                }
+            }
+
+            let retrace = self.reversal == ReversalPolicy::Retrace
+                && !was_finished
+                && state.end_goal != last_end_goal
+                && state.end_goal == state.start_goal;
+
+            if retrace {
+                state.end_goal = last_end_goal.clone();
+                self.retrace(state, &motion);
+            } else {
+                self.start(state, &last_end_goal, progress, was_finished);
+            }
 
-            if self.continuous {
-                state.start_goal = state.start_goal.lerp(&last_end_goal, state.last_delta);
+            if state.completion_pending {
+                previous_goal = Some((last_end_goal, was_finished));
+
+                // Resolve the previous goal's `finished` futures, even if the
+                // caller doesn't notify.
+                cx.notify();
             }
+            state.completion_pending = true;
+            state.generation += 1;
 
-            was_updated = true;
+            new_goal = Some(state.end_goal.clone());
         });
 
+        self.goal_changed(cx, previous_goal, new_goal) || was_queued
+    }
+
+    /// Starts a new animation towards the end goal of the state, from the
+    /// current value (or the initial value, when not continuous), given the
+    /// previous end goal and the progress towards it.
+    fn start(
+        &self,
+        state: &mut TransitionState<T>,
+        last_end_goal: &T,
+        progress: f32,
+        was_finished: bool,
+    ) {
+        let last_start_goal = state.start_goal.clone();
+
+        state.goal_last_updated_at = Some(state.clock.now());
+        state.elapsed_offset = Duration::ZERO;
+
+        if self.continuous {
+            state.start_goal = state.start_goal.lerp(last_end_goal, state.last_delta);
+        } else if state.reversed {
+            // Reversing swapped the initial value out of the start goal.
+            state.start_goal = state.initial_goal.clone();
+        }
+        state.reversed = false;
+
+        state.scaled_duration_secs = self
+            .duration_scaling
+            .as_ref()
+            .filter(|_| !was_finished)
+            .map(|scaling| {
+                scaling.scale(
+                    self.duration_secs,
+                    state.scaled_duration_secs.unwrap_or(self.duration_secs),
+                    progress,
+                    (&last_start_goal, last_end_goal),
+                    (&state.start_goal, &state.end_goal),
+                )
+            });
+    }
+
+    /// Calls the callbacks for a goal change, given the previous goal (and
+    /// whether it was reached) if it was still pending, and the new goal if the
+    /// goal changed. Returns whether the goal changed.
+    fn goal_changed(
+        &self,
+        cx: &mut App,
+        previous_goal: Option<(T, bool)>,
+        new_goal: Option<T>,
+    ) -> bool {
+        // A previous goal that was reached but never evaluated still counts as
+        // completed rather than interrupted.
+        if let Some((goal, was_finished)) = previous_goal {
+            let callback = if was_finished {
+                &self.on_complete
+            } else {
+                &self.on_interrupted
+            };
+
+            if let Some(callback) = callback {
+                callback(&goal, cx);
+            }
+        }
+
+        let was_updated = new_goal.is_some();
+
+        if let Some((on_start, goal)) = self.on_start.as_ref().zip(new_goal) {
+            on_start(&goal, cx);
+        }
+
         was_updated
     }
 
+    /// Plays the current animation backwards, towards the value it started from.
+    ///
+    /// Unlike setting the goal back with [`update`](Self::update), the
+    /// transition retraces the same eased path it has followed so far, taking as
+    /// long to return as it has played, so an ease-out animation eases in on the
+    /// way back. Any delay is skipped, and a repeating transition only plays the
+    /// current repetition back. Reversing a finished transition plays its last
+    /// animation backwards in full.
+    ///
+    /// This is a goal change, so the callbacks are called and
+    /// [`finished`](Self::finished) futures resolve as superseded. Returns
+    /// `false` without doing anything if the transition has nothing to reverse.
+    pub fn reverse(&self, cx: &mut App) -> bool {
+        let mut previous_goal = None;
+        let mut new_goal = None;
+
+        let motion = MotionSettings::get(cx);
+
+        self.state.update(cx, |state, cx| {
+            if state.goal_last_updated_at.is_none() || state.start_goal == state.end_goal {
+                return;
+            }
+
+            state.set_time_scale(motion.time_scale());
+
+            let last_end_goal = state.end_goal.clone();
+            let (_, was_finished) = self.progress(state, &motion);
+
+            self.retrace(state, &motion);
+
+            if state.completion_pending {
+                previous_goal = Some((last_end_goal, was_finished));
+            }
+            state.completion_pending = true;
+            state.generation += 1;
+
+            new_goal = Some(state.end_goal.clone());
+            cx.notify();
+        });
+        *self.cached_value.borrow_mut() = None;
+
+        self.goal_changed(cx, previous_goal, new_goal)
+    }
+
+    /// Returns a future that resolves once the current goal is reached, or once it
+    /// is superseded by a newer goal or a [`reset`](Self::reset).
+    ///
+    /// Like [`on_complete`](Self::on_complete), completion is detected when the
+    /// transition is evaluated, so the transition must keep being rendered for the
+    /// future to resolve. If the goal has already been reached, the future resolves
+    /// immediately.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// cx.spawn(async move |cx| {
+    ///     let finished = cx.update(|cx| {
+    ///         transition.update(cx, |opacity, cx| {
+    ///             *opacity = 0.0;
+    ///             cx.notify();
+    ///         });
+    ///         transition.finished(cx)
+    ///     })?;
+    ///
+    ///     if finished.await == TransitionOutcome::Completed {
+    ///         // Unmount the dialog.
+    ///     }
+    ///     anyhow::Ok(())
+    /// })
+    /// .detach();
+    /// ```
+    pub fn finished(&self, cx: &mut App) -> TransitionFinished {
+        let generation = self.state.read(cx).generation;
+        let this = self.clone();
+
+        TransitionFinished::new(&self.state, cx, move |state, cx| {
+            let (_, finished) = this.progress(state, &MotionSettings::get(cx));
+
+            if state.completed_generation == Some(generation) {
+                Some(TransitionOutcome::Completed)
+            } else if state.generation != generation {
+                Some(TransitionOutcome::Superseded)
+            } else if !state.completion_pending || finished {
+                Some(TransitionOutcome::Completed)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Pauses the transition, freezing it at its current value until
+    /// [`resume`](Self::resume) is called.
+    ///
+    /// A paused transition is still in progress, but doesn't request animation
+    /// frames. If the goal changes while paused, the new animation starts out
+    /// paused as well.
+    pub fn pause(&self, cx: &mut App) {
+        self.state.update(cx, |state, cx| {
+            if state.paused {
+                return;
+            }
+
+            state.elapsed_offset = state.elapsed().unwrap_or_default();
+            state.paused = true;
+            cx.notify();
+        });
+        *self.cached_value.borrow_mut() = None;
+    }
+
+    /// Resumes a transition paused with [`pause`](Self::pause), continuing from
+    /// where it was paused.
+    pub fn resume(&self, cx: &mut App) {
+        let time_scale = MotionSettings::get(cx).time_scale();
+
+        self.state.update(cx, |state, cx| {
+            if !state.paused {
+                return;
+            }
+
+            state.paused = false;
+            state.time_scale = time_scale;
+            if state.goal_last_updated_at.is_some() {
+                state.goal_last_updated_at = Some(state.clock.now());
+            }
+            cx.notify();
+        });
+        *self.cached_value.borrow_mut() = None;
+    }
+
+    /// Returns whether the transition is paused.
+    pub fn is_paused(&self, cx: &App) -> bool {
+        self.state.read(cx).paused
+    }
+
+    /// Jumps to the given linear progress towards the current goal, before
+    /// easing, skipping any delay.
+    ///
+    /// The progress is measured in durations, so when the transition
+    /// [`repeat`](Self::repeat)s, 1.5 is halfway through the second repetition.
+    /// A paused transition stays paused at the new progress, which allows it to
+    /// be scrubbed.
+    pub fn seek(&self, cx: &mut App, progress: f32) {
+        let motion = MotionSettings::get(cx);
+        let (duration_secs, delay) = self.timing(self.state.read(cx), &motion);
+        let elapsed = Duration::try_from_secs_f32(duration_secs * progress.max(0.))
+            .map_or(Duration::MAX, |elapsed| delay.saturating_add(elapsed));
+
+        self.state.update(cx, |state, cx| {
+            state.elapsed_offset = elapsed;
+            state.goal_last_updated_at = Some(state.clock.now());
+            state.time_scale = motion.time_scale();
+            cx.notify();
+        });
+        *self.cached_value.borrow_mut() = None;
+    }
+
+    /// Replaces the clock that this transition's state reads the current time from.
+    ///
+    /// This is useful for transitions created through [`WindowUseTransition`], whose
+    /// state is constructed internally. See [`TransitionState::with_clock`].
+    pub fn set_clock(&self, cx: &mut App, clock: impl Clock + 'static) {
+        self.state.update(cx, |state, _cx| {
+            state.clock = Rc::new(clock);
+        });
+    }
+
     /// Returns the entity ID associated with this transition's state.
     ///
     /// This can be useful for tracking or comparing transitions.
@@ -277,15 +1093,44 @@ impl<T: Lerp + Clone + PartialEq + 'static> Transition<T> {
     ///
     /// This clears all progress and sets both the start and end goals back to
     /// the initial value that was provided when the transition was created.
-    /// The cache is also cleared.
+    /// The cache is also cleared, and any [`finished`](Self::finished) futures
+    /// resolve as superseded. If a goal was still pending,
+    /// [`on_interrupted`](Self::on_interrupted) (or
+    /// [`on_complete`](Self::on_complete), if it had just been reached) is called
+    /// for it, and `on_interrupted` is called for every queued goal.
     pub fn reset(&self, cx: &mut App) {
-        self.state.update(cx, |state, _cx| {
+        let motion = MotionSettings::get(cx);
+
+        let (previous_goal, queued_goals) = self.state.update(cx, |state, cx| {
+            let previous_goal = state.completion_pending.then(|| {
+                let (_, was_finished) = self.progress(state, &motion);
+                (state.end_goal.clone(), was_finished)
+            });
+            let queued_goals = std::mem::take(&mut state.queued_goals);
+
             state.goal_last_updated_at = None;
+            state.elapsed_offset = Duration::ZERO;
+            state.paused = false;
+            state.reversed = false;
+            state.scaled_duration_secs = None;
             state.start_goal = state.initial_goal.clone();
             state.end_goal = state.initial_goal.clone();
             state.last_delta = 0.0;
+            state.completion_pending = false;
+            state.generation += 1;
+            cx.notify();
+
+            (previous_goal, queued_goals)
         });
         *self.cached_value.borrow_mut() = None;
+
+        self.goal_changed(cx, previous_goal, None);
+
+        if let Some(on_interrupted) = &self.on_interrupted {
+            for goal in &queued_goals {
+                on_interrupted(goal, cx);
+            }
+        }
     }
 }
 
@@ -699,4 +1544,939 @@ mod tests {
             }
         });
     }
+
+    #[gpui::test]
+    fn test_transition_with_manual_clock(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let clock = ManualClock::new();
+            let state = cx.new(|_| TransitionState::new(0.0_f32).with_clock(clock.clone()));
+            let transition = Transition::new(state, Duration::from_millis(100));
+
+            transition.update(cx, |val, _cx| {
+                *val = 100.0;
+            });
+
+            clock.advance(Duration::from_millis(37));
+            let (in_progress, value) = transition.raw_evaluate(cx);
+            assert!(in_progress);
+            assert!((value - 37.0).abs() < 0.001);
+            assert!((transition.evaluate_delta(cx) - 0.37).abs() < 0.00001);
+
+            clock.advance(Duration::from_millis(63));
+            let (in_progress, value) = transition.raw_evaluate(cx);
+            assert!(!in_progress);
+            assert_eq!(value, 100.0);
+        });
+    }
+
+    #[gpui::test]
+    fn test_transition_overshoot(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let clock = ManualClock::new();
+            let value = cx.new(|_| TransitionState::new(0.0_f32).with_clock(clock.clone()));
+            let visible = cx
+                .new(|_| TransitionState::new(BoolLerp::<f32>::falsey()).with_clock(clock.clone()));
+
+            let value = Transition::new(value, Duration::from_millis(100))
+                .with_easing(easing::ease_out_back);
+            let visible = Transition::new(visible, Duration::from_millis(100))
+                .with_easing(easing::ease_out_back);
+
+            value.update(cx, |goal, _cx| *goal = 100.0);
+            visible.update(cx, |goal, _cx| *goal = BoolLerp::truthy());
+
+            // Floats extrapolate past the goal, while a `BoolLerp` saturates.
+            clock.advance(Duration::from_millis(80));
+            assert!(value.raw_evaluate(cx).1 > 100.0);
+            assert!(value.evaluate_delta(cx) > 1.0);
+            assert_eq!(visible.raw_evaluate(cx).1.value(), 1.0);
+
+            clock.advance(Duration::from_millis(20));
+            assert_eq!(value.raw_evaluate(cx), (false, 100.0));
+        });
+    }
+
+    #[gpui::test]
+    fn test_transition_pause_and_resume(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let clock = ManualClock::new();
+            let state = cx.new(|_| TransitionState::new(0.0_f32).with_clock(clock.clone()));
+            let transition = Transition::new(state, Duration::from_millis(100));
+
+            transition.update(cx, |val, _cx| *val = 100.0);
+            clock.advance(Duration::from_millis(30));
+
+            transition.pause(cx);
+            assert!(transition.is_paused(cx));
+
+            // The value is frozen, and no animation frames are needed.
+            clock.advance(Duration::from_millis(500));
+            let (in_progress, value) = transition.raw_evaluate(cx);
+            assert!(!in_progress);
+            assert!((value - 30.0).abs() < 0.001);
+            assert!(!transition.is_finished(cx));
+
+            // Playback carries on from where it was paused.
+            transition.resume(cx);
+            assert!(!transition.is_paused(cx));
+            clock.advance(Duration::from_millis(20));
+            let (in_progress, value) = transition.raw_evaluate(cx);
+            assert!(in_progress);
+            assert!((value - 50.0).abs() < 0.001);
+
+            clock.advance(Duration::from_millis(50));
+            assert_eq!(transition.raw_evaluate(cx), (false, 100.0));
+        });
+    }
+
+    #[gpui::test]
+    fn test_transition_seek(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let clock = ManualClock::new();
+            let state = cx.new(|_| TransitionState::new(0.0_f32).with_clock(clock.clone()));
+            let transition = Transition::new(state, Duration::from_millis(100))
+                .with_delay(Duration::from_millis(50));
+
+            transition.update(cx, |val, _cx| *val = 100.0);
+
+            // Seeking skips the delay.
+            transition.seek(cx, 0.25);
+            assert!((transition.raw_evaluate(cx).1 - 25.0).abs() < 0.001);
+
+            clock.advance(Duration::from_millis(25));
+            assert!((transition.raw_evaluate(cx).1 - 50.0).abs() < 0.001);
+
+            // A paused transition can be scrubbed.
+            transition.pause(cx);
+            transition.seek(cx, 0.8);
+            clock.advance(Duration::from_millis(100));
+            assert!((transition.raw_evaluate(cx).1 - 80.0).abs() < 0.001);
+            assert!(transition.is_paused(cx));
+
+            // Changing the goal starts over from the beginning, including the delay.
+            transition.resume(cx);
+            transition.update(cx, |val, _cx| *val = 0.0);
+            clock.advance(Duration::from_millis(50));
+            assert!((transition.raw_evaluate(cx).1 - 80.0).abs() < 0.001);
+        });
+    }
+
+    #[gpui::test]
+    fn test_transition_reverse(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let clock = ManualClock::new();
+            let state = cx.new(|_| TransitionState::new(0.0_f32).with_clock(clock.clone()));
+            let transition = Transition::new(state, Duration::from_millis(100))
+                .with_easing(easing::ease_out_quad);
+
+            assert!(!transition.reverse(cx));
+
+            transition.update(cx, |val, _cx| *val = 100.0);
+            clock.advance(Duration::from_millis(60));
+            let (_, forward) = transition.raw_evaluate(cx);
+            assert!((forward - 84.0).abs() < 0.001);
+
+            // The way back passes through the same values, and takes as long.
+            assert!(transition.reverse(cx));
+            assert_eq!(*transition.read_goal(cx), 0.0);
+            clock.advance(Duration::from_millis(20));
+            assert!((transition.raw_evaluate(cx).1 - 64.0).abs() < 0.001);
+            clock.advance(Duration::from_millis(40));
+            assert_eq!(transition.raw_evaluate(cx), (false, 0.0));
+
+            // Reversing a finished transition plays it back in full.
+            assert!(transition.reverse(cx));
+            clock.advance(Duration::from_millis(40));
+            assert!((transition.raw_evaluate(cx).1 - 64.0).abs() < 0.001);
+
+            // Updating the goal afterwards starts a regular animation.
+            transition.update(cx, |val, _cx| *val = 0.0);
+            clock.advance(Duration::from_millis(50));
+            assert!((transition.raw_evaluate(cx).1 - 16.0).abs() < 0.001);
+        });
+    }
+
+    #[gpui::test]
+    fn test_transition_reversal_policy(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let clock = ManualClock::new();
+            let new_transition = |cx: &mut App, reversal| {
+                let state = cx.new(|_| TransitionState::new(0.0_f32).with_clock(clock.clone()));
+                Transition::new(state, Duration::from_millis(100))
+                    .with_easing(easing::ease_out_quad)
+                    .reversal(reversal)
+            };
+
+            let restarted = new_transition(cx, ReversalPolicy::Restart);
+            let retraced = new_transition(cx, ReversalPolicy::Retrace);
+
+            for transition in [&restarted, &retraced] {
+                transition.update(cx, |val, _cx| *val = 100.0);
+            }
+            clock.advance(Duration::from_millis(50));
+            for transition in [&restarted, &retraced] {
+                assert!((transition.raw_evaluate(cx).1 - 75.0).abs() < 0.001);
+                transition.update(cx, |val, _cx| *val = 0.0);
+            }
+
+            clock.advance(Duration::from_millis(25));
+            // 75 * (1 - ease_out_quad(0.25))
+            assert!((restarted.raw_evaluate(cx).1 - 42.1875).abs() < 0.001);
+            // ease_out_quad(0.25) * 100
+            assert!((retraced.raw_evaluate(cx).1 - 43.75).abs() < 0.001);
+
+            clock.advance(Duration::from_millis(25));
+            assert!(restarted.raw_evaluate(cx).0);
+            assert_eq!(retraced.raw_evaluate(cx), (false, 0.0));
+
+            // Setting the goal to something else doesn't retrace.
+            retraced.update(cx, |val, _cx| *val = 100.0);
+            clock.advance(Duration::from_millis(50));
+            assert!((retraced.raw_evaluate(cx).1 - 75.0).abs() < 0.001);
+            retraced.update(cx, |val, _cx| *val = 50.0);
+            clock.advance(Duration::from_millis(50));
+            assert!((retraced.raw_evaluate(cx).1 - 56.25).abs() < 0.001);
+        });
+    }
+
+    #[gpui::test]
+    fn test_transition_scale_duration(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let clock = ManualClock::new();
+            let state = cx.new(|_| TransitionState::new(0.0_f32).with_clock(clock.clone()));
+            let by_progress = Transition::new(state, Duration::from_millis(400))
+                .scale_duration_by_progress(Duration::from_millis(50), Duration::from_millis(400));
+            let state = cx.new(|_| TransitionState::new(0.0_f32).with_clock(clock.clone()));
+            let by_distance = Transition::new(state, Duration::from_millis(400))
+                .scale_duration_by_distance(Duration::ZERO, Duration::from_millis(800));
+
+            // The first animation isn't interrupting anything.
+            for transition in [&by_progress, &by_distance] {
+                transition.update(cx, |val, _cx| *val = 100.0);
+            }
+            clock.advance(Duration::from_millis(300));
+            for transition in [&by_progress, &by_distance] {
+                assert!((transition.raw_evaluate(cx).1 - 75.0).abs() < 0.001);
+                transition.update(cx, |val, _cx| *val = 95.0);
+            }
+
+            // 25% of the progress was left, and 20% of the distance.
+            clock.advance(Duration::from_millis(80));
+            assert!(by_progress.raw_evaluate(cx).0);
+            assert!((by_distance.raw_evaluate(cx).1 - 95.0).abs() < 0.001);
+            clock.advance(Duration::from_millis(20));
+            assert_eq!(by_progress.raw_evaluate(cx), (false, 95.0));
+
+            // Durations are kept within their bounds.
+            by_progress.update(cx, |val, _cx| *val = 0.0);
+            clock.advance(Duration::from_millis(1));
+            by_progress.raw_evaluate(cx);
+            by_progress.update(cx, |val, _cx| *val = 100.0);
+            clock.advance(Duration::from_millis(399));
+            assert!(by_progress.raw_evaluate(cx).0);
+
+            by_distance.update(cx, |val, _cx| *val = 1000.0);
+            clock.advance(Duration::from_millis(400));
+            by_distance.raw_evaluate(cx);
+            by_distance.update(cx, |val, _cx| *val = 0.0);
+            clock.advance(Duration::from_millis(800));
+            assert!((by_distance.raw_evaluate(cx).1).abs() < 0.001);
+        });
+    }
+
+    #[gpui::test]
+    fn test_transition_scale_duration_repeatedly(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let clock = ManualClock::new();
+            let state = cx.new(|_| TransitionState::new(0.0_f32).with_clock(clock.clone()));
+            let transition = Transition::new(state, Duration::from_millis(400))
+                .scale_duration_by_progress(Duration::ZERO, Duration::from_millis(400));
+
+            transition.update(cx, |val, _cx| *val = 100.0);
+
+            // Each interruption at the halfway point takes half of the full
+            // duration, rather than half of the previous, already scaled one.
+            clock.advance(Duration::from_millis(200));
+            transition.raw_evaluate(cx);
+            for goal in [200.0, 300.0, 400.0] {
+                transition.update(cx, |val, _cx| *val = goal);
+                clock.advance(Duration::from_millis(100));
+                assert!(transition.raw_evaluate(cx).0);
+            }
+
+            clock.advance(Duration::from_millis(100));
+            assert_eq!(transition.raw_evaluate(cx), (false, 400.0));
+        });
+    }
+
+    #[gpui::test]
+    fn test_transition_interrupt_ignore_and_jump(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let clock = ManualClock::new();
+            let new_transition = |cx: &mut App, interrupt| {
+                let state = cx.new(|_| TransitionState::new(0.0_f32).with_clock(clock.clone()));
+                Transition::new(state, Duration::from_millis(100)).interrupt(interrupt)
+            };
+
+            let ignored = new_transition(cx, InterruptPolicy::Ignore);
+            let jumped = new_transition(cx, InterruptPolicy::JumpToEnd);
+
+            for transition in [&ignored, &jumped] {
+                assert!(transition.update(cx, |val, _cx| *val = 100.0));
+            }
+            clock.advance(Duration::from_millis(50));
+            for transition in [&ignored, &jumped] {
+                transition.raw_evaluate(cx);
+            }
+
+            assert!(!ignored.update(cx, |val, _cx| *val = 200.0));
+            assert!(jumped.update(cx, |val, _cx| *val = 200.0));
+
+            clock.advance(Duration::from_millis(50));
+            assert_eq!(ignored.raw_evaluate(cx), (false, 100.0));
+            assert!((jumped.raw_evaluate(cx).1 - 150.0).abs() < 0.001);
+
+            // Once the animation has finished, new goals are taken as usual.
+            assert!(ignored.update(cx, |val, _cx| *val = 200.0));
+            clock.advance(Duration::from_millis(50));
+            assert!((ignored.raw_evaluate(cx).1 - 150.0).abs() < 0.001);
+        });
+    }
+
+    #[gpui::test]
+    fn test_transition_interrupt_queue(cx: &mut TestAppContext) {
+        let clock = ManualClock::new();
+        let events = Rc::new(RefCell::new(Vec::new()));
+
+        let transition = cx.update(|cx| {
+            let state = cx.new(|_| TransitionState::new(0.0_f32).with_clock(clock.clone()));
+            let transition = Transition::new(state, Duration::from_millis(100))
+                .interrupt(InterruptPolicy::Queue(2));
+            record_callbacks(transition, &events)
+        });
+
+        let mut finished = cx.update(|cx| {
+            transition.update(cx, |val, _cx| *val = 1.0);
+            clock.advance(Duration::from_millis(50));
+
+            for (goal, queued) in [(2.0, true), (2.0, false), (3.0, true), (4.0, true)] {
+                assert_eq!(transition.update(cx, |val, _cx| *val = goal), queued);
+            }
+
+            // The oldest goal was dropped to stay within the capacity, and the
+            // duplicate wasn't queued.
+            assert_eq!(*transition.read_goal(cx), 1.0);
+            assert_eq!(transition.read_queue(cx), &VecDeque::from([3.0, 4.0]));
+            assert!(!transition.is_finished(cx));
+
+            transition.finished(cx)
+        });
+
+        clock.advance(Duration::from_millis(50));
+        cx.update(|cx| {
+            // The next goal starts from the goal that was reached.
+            assert_eq!(transition.raw_evaluate(cx), (true, 1.0));
+            assert_eq!(*transition.read_goal(cx), 3.0);
+
+            clock.advance(Duration::from_millis(50));
+            assert!((transition.raw_evaluate(cx).1 - 2.0).abs() < 0.001);
+        });
+        assert_eq!(poll_now(&mut finished), Some(TransitionOutcome::Completed));
+
+        for _ in 0..2 {
+            clock.advance(Duration::from_millis(100));
+            cx.update(|cx| {
+                transition.raw_evaluate(cx);
+            });
+        }
+        cx.update(|cx| {
+            assert_eq!(transition.raw_evaluate(cx), (false, 4.0));
+            assert!(transition.is_finished(cx));
+        });
+
+        assert_eq!(
+            *events.borrow(),
+            vec![
+                ("start", 1.0),
+                ("complete", 1.0),
+                ("start", 3.0),
+                ("complete", 3.0),
+                ("start", 4.0),
+                ("complete", 4.0),
+            ]
+        );
+    }
+
+    #[gpui::test]
+    fn test_transition_velocity(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let clock = ManualClock::new();
+            let state = cx.new(|_| {
+                TransitionState::new(Point {
+                    x: px(0.),
+                    y: px(0.),
+                })
+                .with_clock(clock.clone())
+            });
+            let linear = Transition::new(state, Duration::from_millis(200));
+            let state = cx.new(|_| TransitionState::new(0.0_f32).with_clock(clock.clone()));
+            let eased =
+                Transition::new(state, Duration::from_secs(1)).with_easing(easing::ease_in_quad);
+
+            assert_eq!(eased.evaluate_velocity(cx), vec![0.0]);
+
+            linear.update(cx, |val, _cx| {
+                *val = Point {
+                    x: px(100.),
+                    y: px(-50.),
+                }
+            });
+            eased.update(cx, |val, _cx| *val = 100.0);
+            clock.advance(Duration::from_millis(100));
+
+            let velocity = linear.evaluate_velocity(cx);
+            assert!((velocity[0] - 500.0).abs() < 0.1);
+            assert!((velocity[1] + 250.0).abs() < 0.1);
+            assert!(linear.evaluate_acceleration(cx)[0].abs() < 1.0);
+
+            // The value of the eased transition is 100t², so it moves at 200t
+            // units per second, and accelerates at 200 units per second squared.
+            clock.advance(Duration::from_millis(400));
+            assert!((eased.evaluate_velocity(cx)[0] - 100.0).abs() < 0.1);
+            assert!((eased.evaluate_acceleration(cx)[0] - 200.0).abs() < 1.0);
+
+            // Finished and paused transitions are at rest.
+            eased.pause(cx);
+            assert_eq!(eased.evaluate_velocity(cx), vec![0.0]);
+            eased.resume(cx);
+            clock.advance(Duration::from_millis(500));
+            assert_eq!(eased.evaluate_velocity(cx), vec![0.0]);
+        });
+    }
+
+    #[gpui::test]
+    fn test_transition_velocity_at_start(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let clock = ManualClock::new();
+            let state = cx.new(|_| TransitionState::new(0.0_f32).with_clock(clock.clone()));
+            let linear = Transition::new(state, Duration::from_millis(100));
+            let state = cx.new(|_| TransitionState::new(0.0_f32).with_clock(clock.clone()));
+            let eased =
+                Transition::new(state, Duration::from_secs(1)).with_easing(easing::ease_out_quad);
+
+            linear.update(cx, |val, _cx| *val = 100.0);
+            eased.update(cx, |val, _cx| *val = 100.0);
+
+            // The value of the eased transition is 100(2t - t²), so it starts out
+            // at 200 units per second, slowing down by 200 units per second squared.
+            assert!((linear.evaluate_velocity(cx)[0] - 1000.0).abs() < 0.1);
+            assert!((eased.evaluate_velocity(cx)[0] - 200.0).abs() < 0.1);
+            assert!((eased.evaluate_acceleration(cx)[0] + 200.0).abs() < 1.0);
+        });
+    }
+
+    #[gpui::test]
+    fn test_transition_color_velocity(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let clock = ManualClock::new();
+            let white = LinearRgba(Rgba {
+                r: 1.,
+                g: 1.,
+                b: 1.,
+                a: 1.,
+            });
+            let black = LinearRgba(Rgba {
+                r: 0.,
+                g: 0.,
+                b: 0.,
+                a: 1.,
+            });
+
+            let state = cx.new(|_| TransitionState::new(white).with_clock(clock.clone()));
+            let transition = Transition::new(state, Duration::from_millis(500));
+
+            transition.update(cx, |val, _cx| *val = black);
+            clock.advance(Duration::from_millis(250));
+
+            // The channels darken at 2 per second, and the alpha stays put.
+            let velocity = transition.evaluate_velocity(cx);
+            for channel in &velocity[..3] {
+                assert!((channel + 2.0).abs() < 0.001);
+            }
+            assert_eq!(velocity[3], 0.0);
+        });
+    }
+
+    #[gpui::test]
+    fn test_transition_motion_settings(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let clock = ManualClock::new();
+            let new_transition = |cx: &mut App| {
+                let state = cx.new(|_| TransitionState::new(0.0_f32).with_clock(clock.clone()));
+                Transition::new(state, Duration::from_millis(100))
+            };
+
+            // Time is slowed down to 10%.
+            MotionSettings::set(MotionSettings::default().with_time_scale(0.1), cx);
+            let slowed = new_transition(cx);
+            slowed.update(cx, |val, _cx| *val = 100.0);
+            clock.advance(Duration::from_millis(100));
+            assert!((slowed.raw_evaluate(cx).1 - 10.0).abs() < 0.001);
+
+            // Reduced motion snaps, except for essential transitions.
+            MotionSettings::set(
+                MotionSettings::default().with_reduced_motion(Some(ReducedMotion::Snap)),
+                cx,
+            );
+            let snapped = new_transition(cx);
+            let essential = new_transition(cx).essential(true);
+            snapped.update(cx, |val, _cx| *val = 100.0);
+            essential.update(cx, |val, _cx| *val = 100.0);
+            assert_eq!(snapped.raw_evaluate(cx), (false, 100.0));
+            assert_eq!(essential.raw_evaluate(cx), (true, 0.0));
+        });
+    }
+
+    #[gpui::test]
+    fn test_transition_time_scale_change(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let clock = ManualClock::new();
+            let new_transition = |cx: &mut App| {
+                let state = cx.new(|_| TransitionState::new(0.0_f32).with_clock(clock.clone()));
+                Transition::new(state, Duration::from_millis(100))
+            };
+
+            let running = new_transition(cx);
+            let finished = new_transition(cx);
+            finished.update(cx, |val, _cx| *val = 100.0);
+            clock.advance(Duration::from_millis(150));
+            running.update(cx, |val, _cx| *val = 100.0);
+            clock.advance(Duration::from_millis(50));
+            assert_eq!(finished.raw_evaluate(cx), (false, 100.0));
+
+            // Slowing time down keeps the progress of running transitions, and
+            // doesn't replay finished ones.
+            MotionSettings::set(MotionSettings::default().with_time_scale(0.1), cx);
+            assert!((running.raw_evaluate(cx).1 - 50.0).abs() < 0.001);
+            assert_eq!(finished.raw_evaluate(cx), (false, 100.0));
+
+            clock.advance(Duration::from_millis(100));
+            assert!((running.raw_evaluate(cx).1 - 60.0).abs() < 0.001);
+
+            // A scale of 0 freezes transitions where they are, without
+            // requesting animation frames.
+            MotionSettings::set(MotionSettings::default().with_time_scale(0.), cx);
+            assert!(!running.raw_evaluate(cx).0);
+            clock.advance(Duration::from_millis(500));
+            let (in_progress, value) = running.raw_evaluate(cx);
+            assert!(!in_progress);
+            assert!((value - 60.0).abs() < 0.001);
+            assert_eq!(finished.raw_evaluate(cx), (false, 100.0));
+
+            MotionSettings::set(MotionSettings::default(), cx);
+            assert!(running.raw_evaluate(cx).0);
+            clock.advance(Duration::from_millis(20));
+            assert!((running.raw_evaluate(cx).1 - 80.0).abs() < 0.001);
+        });
+    }
+
+    #[gpui::test]
+    fn test_transition_with_delay(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let clock = ManualClock::new();
+            let state = cx.new(|_| TransitionState::new(0.0_f32).with_clock(clock.clone()));
+            let transition = Transition::new(state, Duration::from_millis(100))
+                .with_delay(Duration::from_millis(150));
+
+            transition.update(cx, |val, _cx| {
+                *val = 100.0;
+            });
+
+            // Nothing moves during the delay, but the transition is still in progress.
+            clock.advance(Duration::from_millis(149));
+            let (in_progress, value) = transition.raw_evaluate(cx);
+            assert!(in_progress);
+            assert_eq!(value, 0.0);
+
+            clock.advance(Duration::from_millis(51));
+            let (in_progress, value) = transition.raw_evaluate(cx);
+            assert!(in_progress);
+            assert!((value - 50.0).abs() < 0.001);
+
+            clock.advance(Duration::from_millis(50));
+            let (in_progress, value) = transition.raw_evaluate(cx);
+            assert!(!in_progress);
+            assert_eq!(value, 100.0);
+        });
+    }
+
+    #[gpui::test]
+    fn test_transition_delay_restarts_on_goal_change(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let clock = ManualClock::new();
+            let state = cx.new(|_| TransitionState::new(0.0_f32).with_clock(clock.clone()));
+            let transition = Transition::new(state, Duration::from_millis(100))
+                .with_delay(Duration::from_millis(100));
+
+            transition.update(cx, |val, _cx| *val = 100.0);
+            clock.advance(Duration::from_millis(150));
+            let (_, value) = transition.raw_evaluate(cx);
+            assert!((value - 50.0).abs() < 0.001);
+
+            // A new goal waits out the delay again, holding the current value.
+            transition.update(cx, |val, _cx| *val = 0.0);
+            clock.advance(Duration::from_millis(50));
+            let (in_progress, value) = transition.raw_evaluate(cx);
+            assert!(in_progress);
+            assert!((value - 50.0).abs() < 0.001);
+        });
+    }
+
+    #[gpui::test]
+    fn test_transition_repeat_alternate(cx: &mut TestAppContext) {
+        let clock = ManualClock::new();
+        let events = Rc::new(RefCell::new(Vec::new()));
+
+        cx.update(|cx| {
+            let state = cx.new(|_| TransitionState::new(0.0_f32).with_clock(clock.clone()));
+            let transition =
+                record_callbacks(Transition::new(state, Duration::from_millis(100)), &events)
+                    .repeat(Repeat::Times(2))
+                    .alternate(true);
+
+            transition.update(cx, |val, _cx| *val = 100.0);
+
+            clock.advance(Duration::from_millis(100));
+            let (in_progress, value) = transition.raw_evaluate(cx);
+            assert!(in_progress);
+            assert_eq!(value, 0.0_f32.lerp(&100.0, 1.0));
+
+            clock.advance(Duration::from_millis(25));
+            let (in_progress, value) = transition.raw_evaluate(cx);
+            assert!(in_progress);
+            assert!((value - 75.0).abs() < 0.001);
+
+            // Two alternating repetitions end back at the start.
+            clock.advance(Duration::from_millis(75));
+            let (in_progress, value) = transition.raw_evaluate(cx);
+            assert!(!in_progress);
+            assert_eq!(value, 0.0);
+        });
+
+        cx.run_until_parked();
+        assert_eq!(
+            *events.borrow(),
+            vec![("start", 100.0), ("complete", 100.0)]
+        );
+    }
+
+    #[gpui::test]
+    fn test_transition_repeat_forever(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let clock = ManualClock::new();
+            let state = cx.new(|_| TransitionState::new(0.0_f32).with_clock(clock.clone()));
+            let transition =
+                Transition::new(state, Duration::from_millis(100)).repeat(Repeat::Forever);
+
+            transition.update(cx, |val, _cx| *val = 100.0);
+
+            clock.advance(Duration::from_millis(10_050));
+            let (in_progress, value) = transition.raw_evaluate(cx);
+            assert!(in_progress);
+            assert!((value - 50.0).abs() < 0.01);
+        });
+    }
+
+    #[gpui::test]
+    fn test_transition_set_clock(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let clock = ManualClock::new();
+            let transition = create_transition(cx, Duration::from_millis(200), 0.0_f32);
+            transition.set_clock(cx, clock.clone());
+
+            transition.update(cx, |val, _cx| {
+                *val = 10.0;
+            });
+
+            // The manual clock hasn't moved, so no progress has been made.
+            assert_eq!(transition.evaluate_delta(cx), 0.0);
+
+            clock.advance(Duration::from_millis(50));
+            assert!((transition.evaluate_delta(cx) - 0.25).abs() < 0.00001);
+        });
+    }
+
+    #[gpui::test]
+    fn test_frame_clock_latches_during_evaluate(cx: &mut TestAppContext) {
+        let clock = FrameClock::new();
+        let window = cx.add_empty_window();
+
+        window.update(|window, cx| {
+            let state = cx.new(|_| TransitionState::new(0.0_f32).with_clock(clock.clone()));
+            let transition = Transition::new(state, Duration::from_millis(300));
+
+            transition.evaluate(window, cx);
+            let frame_time = clock.now();
+            std::thread::sleep(Duration::from_millis(2));
+            assert_eq!(clock.now(), frame_time);
+        });
+    }
+
+    /// Records every callback fired by a transition, in order.
+    fn record_callbacks(
+        transition: Transition<f32>,
+        events: &Rc<RefCell<Vec<(&'static str, f32)>>>,
+    ) -> Transition<f32> {
+        let (start, complete, interrupted) = (events.clone(), events.clone(), events.clone());
+
+        transition
+            .on_start(move |goal, _cx| RefCell::borrow_mut(&start).push(("start", *goal)))
+            .on_complete(move |goal, _cx| RefCell::borrow_mut(&complete).push(("complete", *goal)))
+            .on_interrupted(move |goal, _cx| {
+                RefCell::borrow_mut(&interrupted).push(("interrupted", *goal))
+            })
+    }
+
+    #[gpui::test]
+    fn test_callbacks_start_and_complete(cx: &mut TestAppContext) {
+        let clock = ManualClock::new();
+        let events = Rc::new(RefCell::new(Vec::new()));
+
+        let transition = cx.update(|cx| {
+            let state = cx.new(|_| TransitionState::new(0.0_f32).with_clock(clock.clone()));
+            record_callbacks(Transition::new(state, Duration::from_millis(100)), &events)
+        });
+
+        cx.update(|cx| {
+            transition.update(cx, |val, _cx| *val = 10.0);
+        });
+        assert_eq!(*events.borrow(), vec![("start", 10.0)]);
+
+        clock.advance(Duration::from_millis(50));
+        cx.update(|cx| {
+            transition.raw_evaluate(cx);
+        });
+        assert_eq!(events.borrow().len(), 1);
+
+        clock.advance(Duration::from_millis(50));
+        cx.update(|cx| {
+            transition.raw_evaluate(cx);
+            transition.raw_evaluate(cx);
+        });
+        clock.advance(Duration::from_millis(50));
+        cx.update(|cx| {
+            transition.raw_evaluate(cx);
+        });
+
+        // Completion is only reported once, no matter how often it is evaluated.
+        assert_eq!(*events.borrow(), vec![("start", 10.0), ("complete", 10.0)]);
+    }
+
+    #[gpui::test]
+    fn test_callbacks_interrupted(cx: &mut TestAppContext) {
+        let clock = ManualClock::new();
+        let events = Rc::new(RefCell::new(Vec::new()));
+
+        cx.update(|cx| {
+            let state = cx.new(|_| TransitionState::new(0.0_f32).with_clock(clock.clone()));
+            let transition =
+                record_callbacks(Transition::new(state, Duration::from_millis(100)), &events);
+
+            transition.update(cx, |val, _cx| *val = 10.0);
+            clock.advance(Duration::from_millis(50));
+            transition.update(cx, |val, _cx| *val = 20.0);
+        });
+
+        assert_eq!(
+            *events.borrow(),
+            vec![("start", 10.0), ("interrupted", 10.0), ("start", 20.0)]
+        );
+    }
+
+    #[gpui::test]
+    fn test_callbacks_unevaluated_goal_still_completes(cx: &mut TestAppContext) {
+        let clock = ManualClock::new();
+        let events = Rc::new(RefCell::new(Vec::new()));
+
+        cx.update(|cx| {
+            let state = cx.new(|_| TransitionState::new(0.0_f32).with_clock(clock.clone()));
+            let transition =
+                record_callbacks(Transition::new(state, Duration::from_millis(100)), &events);
+
+            transition.update(cx, |val, _cx| *val = 10.0);
+            clock.advance(Duration::from_millis(150));
+            transition.update(cx, |val, _cx| *val = 20.0);
+        });
+
+        assert_eq!(
+            *events.borrow(),
+            vec![("start", 10.0), ("complete", 10.0), ("start", 20.0)]
+        );
+    }
+
+    #[gpui::test]
+    fn test_callbacks_interrupted_by_reset(cx: &mut TestAppContext) {
+        let clock = ManualClock::new();
+        let events = Rc::new(RefCell::new(Vec::new()));
+
+        cx.update(|cx| {
+            let state = cx.new(|_| TransitionState::new(0.0_f32).with_clock(clock.clone()));
+            let transition = record_callbacks(
+                Transition::new(state, Duration::from_millis(100))
+                    .interrupt(InterruptPolicy::Queue(2)),
+                &events,
+            );
+
+            transition.update(cx, |val, _cx| *val = 10.0);
+            transition.update(cx, |val, _cx| *val = 20.0);
+            clock.advance(Duration::from_millis(50));
+            transition.reset(cx);
+
+            // A reset transition at rest has nothing left to interrupt.
+            transition.reset(cx);
+        });
+
+        assert_eq!(
+            *events.borrow(),
+            vec![
+                ("start", 10.0),
+                ("interrupted", 10.0),
+                ("interrupted", 20.0)
+            ]
+        );
+    }
+
+    #[gpui::test]
+    fn test_callbacks_not_fired_without_goal_change(cx: &mut TestAppContext) {
+        let events = Rc::new(RefCell::new(Vec::new()));
+
+        cx.update(|cx| {
+            let transition = record_callbacks(
+                create_transition(cx, Duration::from_millis(100), 5.0_f32),
+                &events,
+            );
+
+            transition.update(cx, |val, _cx| *val = 5.0);
+            transition.raw_evaluate(cx);
+        });
+
+        assert!(events.borrow().is_empty());
+    }
+
+    fn poll_now(future: &mut TransitionFinished) -> Option<TransitionOutcome> {
+        use std::{future::Future, pin::Pin, task};
+
+        let mut task_cx = task::Context::from_waker(task::Waker::noop());
+        match Pin::new(future).poll(&mut task_cx) {
+            task::Poll::Ready(outcome) => Some(outcome),
+            task::Poll::Pending => None,
+        }
+    }
+
+    #[gpui::test]
+    fn test_finished_resolves_immediately_when_at_rest(cx: &mut TestAppContext) {
+        let mut finished = cx.update(|cx| {
+            let transition = create_transition(cx, Duration::from_millis(100), 0.0_f32);
+            transition.finished(cx)
+        });
+
+        assert_eq!(poll_now(&mut finished), Some(TransitionOutcome::Completed));
+    }
+
+    #[gpui::test]
+    async fn test_finished_resolves_on_completion(cx: &mut TestAppContext) {
+        let clock = ManualClock::new();
+
+        let (transition, mut finished) = cx.update(|cx| {
+            let state = cx.new(|_| TransitionState::new(0.0_f32).with_clock(clock.clone()));
+            let transition = Transition::new(state, Duration::from_millis(100));
+
+            transition.update(cx, |val, _cx| *val = 10.0);
+            let finished = transition.finished(cx);
+            (transition, finished)
+        });
+
+        clock.advance(Duration::from_millis(50));
+        cx.update(|cx| {
+            transition.raw_evaluate(cx);
+        });
+        assert_eq!(poll_now(&mut finished), None);
+
+        clock.advance(Duration::from_millis(50));
+        cx.update(|cx| {
+            transition.raw_evaluate(cx);
+        });
+        assert_eq!(finished.await, TransitionOutcome::Completed);
+    }
+
+    #[gpui::test]
+    async fn test_finished_superseded_by_new_goal(cx: &mut TestAppContext) {
+        let clock = ManualClock::new();
+
+        let (transition, finished) = cx.update(|cx| {
+            let state = cx.new(|_| TransitionState::new(0.0_f32).with_clock(clock.clone()));
+            let transition = Transition::new(state, Duration::from_millis(100));
+
+            transition.update(cx, |val, _cx| *val = 10.0);
+            let finished = transition.finished(cx);
+            (transition, finished)
+        });
+
+        clock.advance(Duration::from_millis(50));
+        cx.update(|cx| {
+            transition.update(cx, |val, cx| {
+                *val = 20.0;
+                cx.notify();
+            });
+        });
+
+        assert_eq!(finished.await, TransitionOutcome::Superseded);
+    }
+
+    #[gpui::test]
+    fn test_finished_resolves_without_notify(cx: &mut TestAppContext) {
+        let clock = ManualClock::new();
+
+        let (replaced, jumped, mut superseded, mut completed) = cx.update(|cx| {
+            let new_transition = |cx: &mut App, interrupt| {
+                let state = cx.new(|_| TransitionState::new(0.0_f32).with_clock(clock.clone()));
+                Transition::new(state, Duration::from_millis(100)).interrupt(interrupt)
+            };
+            let replaced = new_transition(cx, InterruptPolicy::Replace);
+            let jumped = new_transition(cx, InterruptPolicy::JumpToEnd);
+
+            replaced.update(cx, |val, _cx| *val = 10.0);
+            jumped.update(cx, |val, _cx| *val = 10.0);
+            let superseded = replaced.finished(cx);
+            let completed = jumped.finished(cx);
+            (replaced, jumped, superseded, completed)
+        });
+
+        clock.advance(Duration::from_millis(50));
+        cx.update(|cx| {
+            replaced.update(cx, |val, _cx| *val = 20.0);
+            jumped.update(cx, |val, _cx| *val = 20.0);
+        });
+
+        assert_eq!(
+            poll_now(&mut superseded),
+            Some(TransitionOutcome::Superseded)
+        );
+        assert_eq!(poll_now(&mut completed), Some(TransitionOutcome::Completed));
+    }
+
+    #[gpui::test]
+    async fn test_finished_superseded_by_reset(cx: &mut TestAppContext) {
+        let (transition, finished) = cx.update(|cx| {
+            let transition = create_transition(cx, Duration::from_secs(10), 0.0_f32);
+
+            transition.update(cx, |val, _cx| *val = 10.0);
+            let finished = transition.finished(cx);
+            (transition, finished)
+        });
+
+        cx.update(|cx| transition.reset(cx));
+
+        assert_eq!(finished.await, TransitionOutcome::Superseded);
+    }
 }
@@ -0,0 +1,210 @@
+//! App-wide settings that adjust the motion of every transition.
+
+use std::time::Duration;
+
+use gpui::{App, Global};
+
+/// How transitions are adjusted when the user prefers reduced motion.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReducedMotion {
+    /// Transitions jump straight to their goal.
+    Snap,
+
+    /// Transitions skip their delay and take at most the given duration.
+    Shorten(Duration),
+}
+
+/// App-wide settings that every [`Transition`](crate::Transition) consults when it
+/// is evaluated, along with springs, keyframes, gesture releases and timelines.
+///
+/// The settings are stored as a GPUI [`Global`]. Without one, transitions play
+/// exactly as configured. Motion without a fixed duration, such as a spring, is
+/// sped up to fit in a [`ReducedMotion::Shorten`] duration.
+///
+/// # Example
+///
+/// ```ignore
+/// // Honor the user's preference for reduced motion.
+/// MotionSettings::set(
+///     MotionSettings::default().with_reduced_motion(Some(ReducedMotion::Snap)),
+///     cx,
+/// );
+///
+/// // Slow every animation down to 10% while debugging.
+/// MotionSettings::set(MotionSettings::default().with_time_scale(0.1), cx);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MotionSettings {
+    /// The speed at which transitions play, including their delay. A scale of 0.1
+    /// plays every transition at 10% of its speed, and a scale of 0 freezes them.
+    /// Changing the scale keeps running transitions where they are, and doesn't
+    /// replay finished ones. Defaults to 1.
+    pub time_scale: f32,
+
+    /// How transitions are adjusted to reduce motion, or `None` to play them as
+    /// configured. Transitions marked as [`essential`](crate::Transition::essential),
+    /// or their equivalent on the other kinds of motion, are not affected. Defaults to `None`.
+    pub reduced_motion: Option<ReducedMotion>,
+}
+
+impl Default for MotionSettings {
+    fn default() -> Self {
+        Self {
+            time_scale: 1.,
+            reduced_motion: None,
+        }
+    }
+}
+
+impl Global for MotionSettings {}
+
+impl MotionSettings {
+    /// Sets the speed at which transitions play.
+    pub fn with_time_scale(mut self, time_scale: f32) -> Self {
+        self.time_scale = time_scale;
+        self
+    }
+
+    /// Sets how transitions are adjusted to reduce motion.
+    pub fn with_reduced_motion(mut self, reduced_motion: Option<ReducedMotion>) -> Self {
+        self.reduced_motion = reduced_motion;
+        self
+    }
+
+    /// Returns the current settings, or the default settings if none were set.
+    pub fn get(cx: &App) -> Self {
+        cx.try_global::<Self>().copied().unwrap_or_default()
+    }
+
+    /// Replaces the current settings.
+    ///
+    /// Running transitions pick up the new settings the next time they are
+    /// evaluated, usually on the next frame, but views aren't re-rendered
+    /// automatically. Frozen transitions don't request animation frames, so
+    /// views need to be re-rendered after unfreezing them.
+    pub fn set(settings: Self, cx: &mut App) {
+        cx.set_global(settings);
+    }
+
+    /// Returns the time scale, with negative and NaN scales treated as 0.
+    pub(crate) fn time_scale(&self) -> f32 {
+        self.time_scale.max(0.)
+    }
+
+    /// Applies the reduced motion of these settings to the duration (in seconds)
+    /// and delay of a transition, returning the ones it should play with.
+    ///
+    /// The time scale isn't applied here, but to the time that has elapsed, with
+    /// [`scale_elapsed`], so that changing it doesn't move transitions that have
+    /// already started.
+    pub(crate) fn apply(
+        &self,
+        duration_secs: f32,
+        delay: Duration,
+        essential: bool,
+    ) -> (f32, Duration) {
+        match self.reduced_motion.filter(|_| !essential) {
+            None => (duration_secs, delay),
+            Some(ReducedMotion::Snap) => (0., Duration::ZERO),
+            Some(ReducedMotion::Shorten(max)) => {
+                (duration_secs.min(max.as_secs_f32()), Duration::ZERO)
+            }
+        }
+    }
+
+    /// Returns the rate at which a motion without a fixed duration, such as a
+    /// spring, plays relative to real time, given roughly how long (in seconds) it
+    /// takes at its own pace. This combines the time scale with the reduced
+    /// motion, which speeds the motion up to fit in the shortened duration.
+    ///
+    /// Returns `None` if the motion should jump straight to its end.
+    pub(crate) fn playback_rate(&self, natural_secs: f32, essential: bool) -> Option<f32> {
+        let speed_up = match self.reduced_motion.filter(|_| !essential) {
+            None => 1.,
+            Some(ReducedMotion::Snap) => return None,
+            Some(ReducedMotion::Shorten(max)) => {
+                let max_secs = max.as_secs_f32();
+                if max_secs <= 0. {
+                    return None;
+                }
+
+                (natural_secs / max_secs).max(1.)
+            }
+        };
+
+        Some(self.time_scale() * speed_up)
+    }
+}
+
+/// Scales an amount of real time by a time scale, returning how much of it a
+/// transition has played. An infinite scale plays everything at once.
+pub(crate) fn scale_elapsed(elapsed: Duration, time_scale: f32) -> Duration {
+    if time_scale == 1. {
+        return elapsed;
+    }
+
+    if time_scale == f32::INFINITY {
+        return Duration::MAX;
+    }
+
+    Duration::try_from_secs_f64(elapsed.as_secs_f64() * time_scale as f64).unwrap_or(Duration::MAX)
+}
+
+#[cfg(all(test, feature = "test-support"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_reduced_motion() {
+        let snap = MotionSettings::default().with_reduced_motion(Some(ReducedMotion::Snap));
+        assert_eq!(
+            snap.apply(1., Duration::from_millis(100), false),
+            (0., Duration::ZERO)
+        );
+
+        let shorten = MotionSettings::default()
+            .with_reduced_motion(Some(ReducedMotion::Shorten(Duration::from_millis(250))));
+        assert_eq!(
+            shorten.apply(1., Duration::from_millis(100), false),
+            (0.25, Duration::ZERO)
+        );
+        assert_eq!(
+            shorten.apply(0.1, Duration::ZERO, false),
+            (0.1, Duration::ZERO)
+        );
+
+        // Essential transitions are not affected.
+        assert_eq!(
+            snap.apply(1., Duration::from_millis(100), true),
+            (1., Duration::from_millis(100))
+        );
+    }
+
+    #[test]
+    fn test_scale_elapsed() {
+        let elapsed = Duration::from_millis(400);
+
+        assert_eq!(scale_elapsed(elapsed, 1.), elapsed);
+        assert_eq!(scale_elapsed(elapsed, 0.5), Duration::from_millis(200));
+        assert_eq!(scale_elapsed(elapsed, 0.), Duration::ZERO);
+
+        let settings = MotionSettings::default().with_time_scale(-1.);
+        assert_eq!(settings.time_scale(), 0.);
+    }
+
+    #[test]
+    fn test_playback_rate() {
+        let settings = MotionSettings::default().with_time_scale(0.5);
+        assert_eq!(settings.playback_rate(1., false), Some(0.5));
+
+        // Reduced motion speeds motion up to fit, or snaps it.
+        let shorten =
+            settings.with_reduced_motion(Some(ReducedMotion::Shorten(Duration::from_millis(250))));
+        assert_eq!(shorten.playback_rate(1., false), Some(2.));
+        assert_eq!(shorten.playback_rate(0.1, false), Some(0.5));
+        assert_eq!(shorten.playback_rate(1., true), Some(0.5));
+
+        let snap = settings.with_reduced_motion(Some(ReducedMotion::Snap));
+        assert_eq!(snap.playback_rate(1., false), None);
+    }
+}
@@ -0,0 +1,184 @@
+//! Repeating, alternating, reversed and interrupted playback of transitions.
+
+/// How many times a [`Transition`](crate::Transition) plays before it finishes.
+///
+/// # Example
+///
+/// ```ignore
+/// // A pulsing badge that fades in and out forever.
+/// let pulse = window
+///     .use_transition(cx, Duration::from_millis(800), |_window, _cx| 1.0_f32)
+///     .repeat(Repeat::Forever)
+///     .alternate(true);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Repeat {
+    /// Plays the given number of times in total. `Times(1)` plays once, which is
+    /// the default, and `Times(0)` finishes immediately.
+    Times(u32),
+
+    /// Plays until the goal changes or the transition is reset.
+    Forever,
+}
+
+impl Default for Repeat {
+    fn default() -> Self {
+        Repeat::Times(1)
+    }
+}
+
+impl Repeat {
+    fn iterations(self) -> f32 {
+        match self {
+            Repeat::Times(times) => times as f32,
+            Repeat::Forever => f32::INFINITY,
+        }
+    }
+}
+
+/// What a [`Transition`](crate::Transition) does when its goal is set back to
+/// the value it started from before it reached its goal.
+///
+/// # Example
+///
+/// ```ignore
+/// // Hovering out retraces the hover in, instead of easing out again.
+/// let hover = window
+///     .use_transition(cx, Duration::from_millis(200), |_window, _cx| 0.0_f32)
+///     .with_easing(easing::ease_out_cubic)
+///     .reversal(ReversalPolicy::Retrace);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ReversalPolicy {
+    /// Starts a new animation towards the goal, easing from the current value
+    /// over the full duration, like any other goal change. This is the default.
+    #[default]
+    Restart,
+
+    /// Plays the current animation backwards, along the same eased path and over
+    /// the time it has played so far, as [`Transition::reverse`](crate::Transition::reverse) does.
+    Retrace,
+}
+
+/// What a [`Transition`](crate::Transition) does when its goal changes before
+/// the previous goal has been reached.
+///
+/// # Example
+///
+/// ```ignore
+/// // Step through every state of a step indicator, even when clicking quickly.
+/// let step = window
+///     .use_transition(cx, Duration::from_millis(250), |_window, _cx| 0.0_f32)
+///     .interrupt(InterruptPolicy::Queue(4));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum InterruptPolicy {
+    /// Animates towards the new goal straight away. This is the default.
+    #[default]
+    Replace,
+
+    /// Keeps animating towards the previous goal, and ignores the new one.
+    Ignore,
+
+    /// Waits for the previous goal to be reached, then animates to the new one.
+    ///
+    /// Up to the given number of goals are queued, after which the oldest queued
+    /// goal is dropped to make room. A goal equal to the last queued goal isn't
+    /// queued again. Queued goals are started when the transition is evaluated.
+    Queue(usize),
+
+    /// Jumps to the previous goal, as if it had been reached, then animates to
+    /// the new one from there.
+    JumpToEnd,
+}
+
+/// Maps the number of iterations elapsed since the transition started onto the
+/// progress (between 0 and 1) within the current iteration.
+///
+/// Returns the progress and whether every iteration has finished. When
+/// `alternate` is set, every other iteration plays backwards.
+pub(crate) fn iteration_progress(elapsed: f32, repeat: Repeat, alternate: bool) -> (f32, bool) {
+    let iterations = repeat.iterations();
+
+    let (iteration, progress, finished) = if elapsed >= iterations {
+        ((iterations - 1.).max(0.), 1., true)
+    } else {
+        let iteration = elapsed.floor();
+        (iteration, elapsed - iteration, false)
+    };
+
+    if alternate && iteration % 2. == 1. {
+        (1. - progress, finished)
+    } else {
+        (progress, finished)
+    }
+}
+
+#[cfg(all(test, feature = "test-support"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plays_once_by_default() {
+        assert_eq!(
+            iteration_progress(0.5, Repeat::default(), false),
+            (0.5, false)
+        );
+        assert_eq!(
+            iteration_progress(1.0, Repeat::default(), false),
+            (1.0, true)
+        );
+        assert_eq!(
+            iteration_progress(3.0, Repeat::default(), false),
+            (1.0, true)
+        );
+    }
+
+    #[test]
+    fn test_repeat_times() {
+        assert_eq!(
+            iteration_progress(1.25, Repeat::Times(3), false),
+            (0.25, false)
+        );
+        assert_eq!(
+            iteration_progress(2.5, Repeat::Times(3), false),
+            (0.5, false)
+        );
+        assert_eq!(
+            iteration_progress(3.0, Repeat::Times(3), false),
+            (1.0, true)
+        );
+        assert_eq!(
+            iteration_progress(0.0, Repeat::Times(0), false),
+            (1.0, true)
+        );
+    }
+
+    #[test]
+    fn test_repeat_forever() {
+        assert_eq!(
+            iteration_progress(1000.5, Repeat::Forever, false),
+            (0.5, false)
+        );
+    }
+
+    #[test]
+    fn test_alternate() {
+        assert_eq!(
+            iteration_progress(0.25, Repeat::Forever, true),
+            (0.25, false)
+        );
+        assert_eq!(
+            iteration_progress(1.25, Repeat::Forever, true),
+            (0.75, false)
+        );
+        assert_eq!(
+            iteration_progress(2.25, Repeat::Forever, true),
+            (0.25, false)
+        );
+
+        // An even number of alternating iterations ends back at the start.
+        assert_eq!(iteration_progress(2.0, Repeat::Times(2), true), (0.0, true));
+        assert_eq!(iteration_progress(3.0, Repeat::Times(3), true), (1.0, true));
+    }
+}
@@ -0,0 +1,434 @@
+//! An element that keeps children mounted while they animate out.
+
+use std::{mem, rc::Rc, time::Duration};
+
+use gpui::{
+    AnyElement, App, Div, ElementId, IntoElement, RenderOnce, StyleRefinement, Styled, Window, div,
+    linear, prelude::*,
+};
+
+use crate::{BoolLerp, WindowUseTransition};
+
+/// Renders a child of a [`Presence`], given how present it is.
+///
+/// The `f32` is 0 while the child is fully absent and 1 once it has fully
+/// entered, and can be used to drive its opacity, scale, offset and so on.
+pub type PresenceRender = Rc<dyn Fn(f32, &mut Window, &mut App) -> AnyElement>;
+
+/// The duration and easing used when a child of a [`Presence`] enters or exits.
+#[derive(Clone)]
+pub struct PresenceTransition {
+    duration: Duration,
+    easing: Rc<dyn Fn(f32) -> f32>,
+}
+
+impl PresenceTransition {
+    /// Creates a new linear presence transition with the given duration.
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            easing: Rc::new(linear),
+        }
+    }
+
+    /// Sets the easing function of this presence transition.
+    pub fn with_easing(mut self, easing: impl Fn(f32) -> f32 + 'static) -> Self {
+        self.easing = Rc::new(easing);
+        self
+    }
+}
+
+impl Default for PresenceTransition {
+    fn default() -> Self {
+        Self::new(Duration::from_millis(200))
+    }
+}
+
+/// A keyed child of a [`Presence`].
+///
+/// Rather than an element, a child is a closure that renders it. This allows the
+/// presence to keep rendering the child after its parent stopped providing it,
+/// for as long as its exit transition runs.
+pub struct PresenceChild {
+    key: ElementId,
+    render: PresenceRender,
+    enter: Option<PresenceTransition>,
+    exit: Option<PresenceTransition>,
+}
+
+impl PresenceChild {
+    /// Creates a new child with the given key, which identifies it across renders.
+    pub fn new(
+        key: impl Into<ElementId>,
+        render: impl Fn(f32, &mut Window, &mut App) -> AnyElement + 'static,
+    ) -> Self {
+        Self {
+            key: key.into(),
+            render: Rc::new(render),
+            enter: None,
+            exit: None,
+        }
+    }
+
+    /// Overrides the transition used when this child enters.
+    pub fn enter(mut self, enter: PresenceTransition) -> Self {
+        self.enter = Some(enter);
+        self
+    }
+
+    /// Overrides the transition used when this child exits.
+    pub fn exit(mut self, exit: PresenceTransition) -> Self {
+        self.exit = Some(exit);
+        self
+    }
+}
+
+#[derive(Clone)]
+struct PresenceEntry {
+    key: ElementId,
+    render: PresenceRender,
+    enter: PresenceTransition,
+    exit: PresenceTransition,
+
+    /// Whether the child is still provided by the parent. Children that aren't
+    /// are exiting.
+    present: bool,
+}
+
+#[derive(Default)]
+struct PresenceState {
+    entries: Vec<PresenceEntry>,
+    mounted: bool,
+}
+
+impl PresenceState {
+    /// Replaces the entries with the given children, keeping the entries that are
+    /// no longer present in their previous position so they can exit in place.
+    fn sync(&mut self, children: Vec<PresenceEntry>) {
+        let previous = mem::replace(&mut self.entries, children);
+
+        for (index, entry) in previous.iter().enumerate() {
+            if self.entries.iter().any(|child| child.key == entry.key) {
+                continue;
+            }
+
+            // Place the exiting entry after the closest entry that preceded it.
+            let insert_at = previous[..index]
+                .iter()
+                .rev()
+                .find_map(|before| self.entries.iter().position(|e| e.key == before.key))
+                .map_or(0, |position| position + 1);
+
+            self.entries.insert(
+                insert_at,
+                PresenceEntry {
+                    present: false,
+                    ..entry.clone()
+                },
+            );
+        }
+    }
+}
+
+/// Creates a new [`Presence`] with the given ID.
+pub fn presence(id: impl Into<ElementId>) -> Presence {
+    Presence {
+        id: id.into(),
+        base: div(),
+        children: Vec::new(),
+        enter: PresenceTransition::default(),
+        exit: PresenceTransition::default(),
+        initial: true,
+    }
+}
+
+/// An element that animates its children in when they're added and out when
+/// they're removed, similar to Framer Motion's `AnimatePresence`.
+///
+/// GPUI drops an element as soon as its parent stops rendering it. A `Presence`
+/// remembers each keyed child, and keeps rendering children that were removed
+/// until their exit transition has finished. Each child's presence is driven by
+/// a keyed [`Transition<BoolLerp<f32>>`](crate::Transition), which is continuous,
+/// so a child that is re-added while exiting animates back in from where it was.
+///
+/// # Example
+///
+/// ```ignore
+/// presence("toasts")
+///     .flex()
+///     .flex_col()
+///     .exit(PresenceTransition::new(Duration::from_millis(300)).with_easing(ease_in_out))
+///     .children(self.toasts.iter().map(|toast| {
+///         let message = toast.message.clone();
+///         PresenceChild::new(toast.id, move |presence, _window, _cx| {
+///             div().opacity(presence).child(message.clone()).into_any_element()
+///         })
+///     }))
+/// ```
+#[derive(IntoElement)]
+pub struct Presence {
+    id: ElementId,
+    base: Div,
+    children: Vec<PresenceChild>,
+    enter: PresenceTransition,
+    exit: PresenceTransition,
+    initial: bool,
+}
+
+impl Presence {
+    /// Sets the transition used when children enter, unless overridden by the child.
+    pub fn enter(mut self, enter: PresenceTransition) -> Self {
+        self.enter = enter;
+        self
+    }
+
+    /// Sets the transition used when children exit, unless overridden by the child.
+    pub fn exit(mut self, exit: PresenceTransition) -> Self {
+        self.exit = exit;
+        self
+    }
+
+    /// Sets whether the children present when the element is first rendered
+    /// animate in. Defaults to true.
+    pub fn initial(mut self, initial: bool) -> Self {
+        self.initial = initial;
+        self
+    }
+
+    /// Adds a child.
+    pub fn child(mut self, child: PresenceChild) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    /// Adds multiple children.
+    pub fn children(mut self, children: impl IntoIterator<Item = PresenceChild>) -> Self {
+        self.children.extend(children);
+        self
+    }
+}
+
+impl Styled for Presence {
+    fn style(&mut self) -> &mut StyleRefinement {
+        self.base.style()
+    }
+}
+
+impl RenderOnce for Presence {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let state =
+            window.use_keyed_state(self.id.clone(), cx, |_window, _cx| PresenceState::default());
+
+        let children = self
+            .children
+            .into_iter()
+            .map(|child| PresenceEntry {
+                key: child.key,
+                render: child.render,
+                enter: child.enter.unwrap_or_else(|| self.enter.clone()),
+                exit: child.exit.unwrap_or_else(|| self.exit.clone()),
+                present: true,
+            })
+            .collect();
+
+        let (entries, first_render) = state.update(cx, |state, _cx| {
+            state.sync(children);
+            (
+                state.entries.clone(),
+                !mem::replace(&mut state.mounted, true),
+            )
+        });
+
+        let initially_present = first_render && !self.initial;
+        let mut exited = Vec::new();
+        let mut elements = Vec::with_capacity(entries.len());
+
+        window.with_id(self.id.clone(), |window| {
+            for entry in entries {
+                let config = if entry.present {
+                    &entry.enter
+                } else {
+                    &entry.exit
+                };
+                let easing = config.easing.clone();
+
+                let transition = window
+                    .use_keyed_transition(entry.key.clone(), cx, config.duration, |_window, _cx| {
+                        BoolLerp::<f32>::from(initially_present)
+                    })
+                    .with_easing(move |delta| easing(delta));
+
+                transition.update(cx, |goal, _cx| *goal = entry.present.into());
+
+                // Unmount the child based on the same evaluation that produces its
+                // value, so it can't finish in between and be left mounted at 0.
+                let (in_progress, presence) = transition.evaluate_uncached(window, cx);
+
+                if !entry.present && !in_progress {
+                    exited.push(entry.key);
+                    continue;
+                }
+
+                elements.push((entry.render)(presence.value(), window, cx));
+            }
+        });
+
+        if !exited.is_empty() {
+            state.update(cx, |state, _cx| {
+                state
+                    .entries
+                    .retain(|entry| entry.present || !exited.contains(&entry.key));
+            });
+        }
+
+        self.base.id(self.id).children(elements)
+    }
+}
+
+#[cfg(all(test, feature = "test-support"))]
+mod tests {
+    use super::*;
+    use gpui::{Context, Entity, Render, TestAppContext, VisualTestContext};
+    use std::cell::RefCell;
+
+    fn entry(key: &'static str) -> PresenceEntry {
+        PresenceEntry {
+            key: key.into(),
+            render: Rc::new(|_, _, _| div().into_any_element()),
+            enter: PresenceTransition::default(),
+            exit: PresenceTransition::default(),
+            present: true,
+        }
+    }
+
+    fn keys(state: &PresenceState) -> Vec<(String, bool)> {
+        state
+            .entries
+            .iter()
+            .map(|entry| (entry.key.to_string(), entry.present))
+            .collect()
+    }
+
+    #[test]
+    fn test_sync_keeps_exiting_entries_in_place() {
+        let mut state = PresenceState::default();
+        state.sync(vec![entry("a"), entry("b"), entry("c")]);
+        state.sync(vec![entry("a"), entry("c"), entry("d")]);
+
+        assert_eq!(
+            keys(&state),
+            vec![
+                ("a".into(), true),
+                ("b".into(), false),
+                ("c".into(), true),
+                ("d".into(), true)
+            ]
+        );
+
+        state.sync(vec![entry("d")]);
+        assert_eq!(
+            keys(&state),
+            vec![
+                ("a".into(), false),
+                ("b".into(), false),
+                ("c".into(), false),
+                ("d".into(), true)
+            ]
+        );
+
+        // A re-added child becomes present again.
+        state.sync(vec![entry("b"), entry("d")]);
+        assert_eq!(
+            keys(&state),
+            vec![
+                ("a".into(), false),
+                ("b".into(), true),
+                ("c".into(), false),
+                ("d".into(), true)
+            ]
+        );
+    }
+
+    struct TestView {
+        children: Vec<&'static str>,
+        exit: Duration,
+        rendered: Rc<RefCell<Vec<(&'static str, f32)>>>,
+    }
+
+    impl Render for TestView {
+        fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+            // Only keep what was rendered in the latest frame.
+            self.rendered.take();
+
+            presence("presence")
+                .initial(false)
+                .exit(PresenceTransition::new(self.exit))
+                .children(self.children.iter().map(|&key| {
+                    let rendered = self.rendered.clone();
+                    PresenceChild::new(key, move |presence, _window, _cx| {
+                        RefCell::borrow_mut(&rendered).push((key, presence));
+                        div().into_any_element()
+                    })
+                }))
+        }
+    }
+
+    /// Renders a presence with the given children, returning the presence of each
+    /// child that was rendered.
+    fn render(
+        view: &Entity<TestView>,
+        cx: &mut VisualTestContext,
+        children: &[&'static str],
+    ) -> Vec<(&'static str, f32)> {
+        view.update(cx, |view, cx| {
+            view.children = children.to_vec();
+            cx.notify();
+        });
+        cx.run_until_parked();
+
+        view.read_with(cx, |view, _cx| view.rendered.take())
+    }
+
+    fn add_view<'a>(
+        cx: &'a mut TestAppContext,
+        children: &[&'static str],
+        exit: Duration,
+    ) -> (Entity<TestView>, &'a mut VisualTestContext) {
+        cx.add_window_view(|_window, _cx| TestView {
+            children: children.to_vec(),
+            exit,
+            rendered: Rc::default(),
+        })
+    }
+
+    #[gpui::test]
+    fn test_presence_keeps_exiting_child_mounted(cx: &mut TestAppContext) {
+        let (view, cx) = add_view(cx, &["a", "b"], Duration::from_secs(60));
+
+        // Children present on the first render don't animate in.
+        assert_eq!(render(&view, cx, &["a", "b"]), vec![("a", 1.0), ("b", 1.0)]);
+
+        let rendered = render(&view, cx, &["a"]);
+        assert_eq!(rendered.len(), 2);
+        assert_eq!(rendered[1].0, "b");
+        assert!(rendered[1].1 > 0.9);
+    }
+
+    #[gpui::test]
+    fn test_presence_unmounts_after_exit(cx: &mut TestAppContext) {
+        let (view, cx) = add_view(cx, &["a", "b"], Duration::ZERO);
+
+        assert_eq!(render(&view, cx, &["a"]), vec![("a", 1.0)]);
+    }
+
+    #[gpui::test]
+    fn test_presence_enters_new_children(cx: &mut TestAppContext) {
+        let (view, cx) = add_view(cx, &["a"], Duration::from_secs(60));
+
+        let rendered = render(&view, cx, &["a", "b"]);
+
+        assert_eq!(rendered[0], ("a", 1.0));
+        assert_eq!(rendered[1].0, "b");
+        assert!(rendered[1].1 < 0.1);
+    }
+}
@@ -0,0 +1,154 @@
+//! Scaling the duration of transitions that interrupt one another.
+
+use std::rc::Rc;
+
+use crate::Components;
+
+/// A trait for measuring how far apart two values are.
+///
+/// It is used by [`Transition::scale_duration_by_distance`](crate::Transition::scale_duration_by_distance)
+/// to give shorter animations to goals that are closer.
+///
+/// # Implementations
+///
+/// Every type that implements [`Components`] measures the Euclidean distance
+/// between its components, so a [`Point`](gpui::Point) measures the straight
+/// line between two points, and an [`Rgba`](gpui::Rgba) the distance between its
+/// channels. Other types can implement this trait themselves.
+pub trait Distance {
+    /// Returns the distance between `self` and `other`, which must not be negative.
+    fn distance(&self, other: &Self) -> f32;
+}
+
+impl<T: Components> Distance for T {
+    fn distance(&self, other: &Self) -> f32 {
+        self.to_components()
+            .into_iter()
+            .zip(other.to_components())
+            .map(|(a, b)| (a - b) * (a - b))
+            .sum::<f32>()
+            .sqrt()
+    }
+}
+
+/// Measures the distance between two values.
+type DistanceFn<T> = Rc<dyn Fn(&T, &T) -> f32>;
+
+/// What the duration of an interrupting animation is scaled by.
+#[derive(Clone)]
+pub(crate) enum ScaleBy<T> {
+    /// The progress that the interrupted animation had left.
+    RemainingProgress,
+
+    /// The distance to the new goal, relative to the distance of the
+    /// interrupted animation.
+    Distance(DistanceFn<T>),
+}
+
+/// How a [`Transition`](crate::Transition) scales the duration of an animation
+/// that starts before the previous one has finished.
+#[derive(Clone)]
+pub(crate) struct DurationScaling<T> {
+    pub(crate) scale_by: ScaleBy<T>,
+    pub(crate) min_secs: f32,
+    pub(crate) max_secs: f32,
+}
+
+impl<T> DurationScaling<T> {
+    /// Returns the duration (in seconds) of an animation from `start` to `goal`,
+    /// which interrupts an animation from `previous_start` to `previous_goal` that
+    /// lasted `previous_secs` at the given progress.
+    ///
+    /// The remaining progress scales the transition's configured `duration_secs`,
+    /// so that repeated interruptions don't compound. The distance scales the
+    /// interrupted animation's own duration instead, which keeps the speed of
+    /// the interrupted animation when it was itself shortened or lengthened.
+    pub(crate) fn scale(
+        &self,
+        duration_secs: f32,
+        previous_secs: f32,
+        progress: f32,
+        (previous_start, previous_goal): (&T, &T),
+        (start, goal): (&T, &T),
+    ) -> f32 {
+        let scaled_secs = match &self.scale_by {
+            ScaleBy::RemainingProgress => duration_secs * (1. - progress.clamp(0., 1.)),
+            ScaleBy::Distance(distance) => {
+                let previous_distance = distance(previous_start, previous_goal);
+
+                if previous_distance > 0. {
+                    previous_secs * distance(start, goal) / previous_distance
+                } else {
+                    previous_secs
+                }
+            }
+        };
+
+        scaled_secs.max(self.min_secs).min(self.max_secs)
+    }
+}
+
+#[cfg(all(test, feature = "test-support"))]
+mod tests {
+    use super::*;
+    use gpui::{Point, px};
+
+    #[test]
+    fn test_distance() {
+        assert_eq!(3.0_f32.distance(&-1.0), 4.0);
+        assert_eq!(px(10.).distance(&px(4.)), 6.0);
+        assert_eq!(
+            Point { x: 0.0_f32, y: 0.0 }.distance(&Point { x: 3.0, y: 4.0 }),
+            5.0
+        );
+    }
+
+    #[test]
+    fn test_scale_by_remaining_progress() {
+        let scaling = DurationScaling {
+            scale_by: ScaleBy::RemainingProgress,
+            min_secs: 0.1,
+            max_secs: 1.,
+        };
+
+        // The previous duration is ignored, so interruptions don't compound.
+        let scale = |progress| scaling.scale(0.4, 0.2, progress, (&0., &1.), (&0.9, &2.));
+        assert!((scale(0.5) - 0.2).abs() < 0.0001);
+        assert_eq!(scale(0.9), 0.1);
+    }
+
+    #[test]
+    fn test_scale_by_distance() {
+        let scaling = DurationScaling {
+            scale_by: ScaleBy::Distance(Rc::new(f32::distance)),
+            min_secs: 0.,
+            max_secs: 0.6,
+        };
+
+        assert!((scaling.scale(0.4, 0.4, 0.5, (&0., &100.), (&50., &60.)) - 0.04).abs() < 0.0001);
+        assert_eq!(
+            scaling.scale(0.4, 0.4, 0.5, (&0., &100.), (&50., &-250.)),
+            0.6
+        );
+
+        // Without a previous distance to compare to, the duration is kept.
+        assert_eq!(scaling.scale(0.4, 0.4, 0.5, (&0., &0.), (&0., &10.)), 0.4);
+    }
+
+    #[test]
+    fn test_scale_by_distance_chained() {
+        let scaling = DurationScaling {
+            scale_by: ScaleBy::Distance(Rc::new(f32::distance)),
+            min_secs: 0.,
+            max_secs: 1.,
+        };
+
+        // 0 to 100 over 400ms is retargeted at 50 to 60, which takes 40ms.
+        let first = scaling.scale(0.4, 0.4, 0.5, (&0., &100.), (&50., &60.));
+        assert!((first - 0.04).abs() < 0.0001);
+
+        // Retargeting again at 55 to 65 covers the same distance at the same speed.
+        let second = scaling.scale(0.4, first, 0.5, (&50., &60.), (&55., &65.));
+        assert!((second - 0.04).abs() < 0.0001);
+    }
+}
@@ -0,0 +1,334 @@
+//! Shared-element transitions, where an element morphs into another element
+//! with the same tag.
+
+use std::{collections::HashMap, panic, rc::Rc, time::Duration};
+
+use gpui::{
+    AnyElement, App, AppContext, Bounds, ContentMask, Corners, Element, ElementId, Entity, Global,
+    GlobalElementId, InspectorElementId, IntoElement, LayoutId, Pixels, Rgba, Window, fill, linear,
+};
+
+use crate::{Lerp, Transition, TransitionState};
+
+/// The visual properties of a [`SharedElement`] that are carried over to the
+/// next element with the same tag.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SharedElementSnapshot {
+    /// The bounds of the element within the window.
+    pub bounds: Bounds<Pixels>,
+
+    /// The radii of the element's corners.
+    pub corner_radii: Corners<Pixels>,
+
+    /// The background color of the element.
+    pub background: Rgba,
+}
+
+impl Lerp for SharedElementSnapshot {
+    fn lerp(&self, to: &Self, delta: f32) -> Self {
+        SharedElementSnapshot {
+            bounds: self.bounds.lerp(&to.bounds, delta),
+            corner_radii: self.corner_radii.lerp(&to.corner_radii, delta),
+            background: self.background.lerp(&to.background, delta),
+        }
+    }
+}
+
+/// A global registry of the last snapshot published by each [`SharedElement`],
+/// keyed by tag.
+///
+/// Every shared element publishes its snapshot whenever it is rendered. When a
+/// shared element is first rendered, it starts from the snapshot of the element
+/// that last published under its tag.
+#[derive(Default)]
+pub struct SharedElementRegistry {
+    snapshots: HashMap<ElementId, SharedElementSnapshot>,
+}
+
+impl Global for SharedElementRegistry {}
+
+impl SharedElementRegistry {
+    /// Returns the last snapshot published under the given tag.
+    pub fn get(tag: &ElementId, cx: &App) -> Option<SharedElementSnapshot> {
+        cx.try_global::<Self>()?.snapshots.get(tag).copied()
+    }
+
+    /// Publishes a snapshot under the given tag, replacing the previous one.
+    pub fn publish(tag: ElementId, snapshot: SharedElementSnapshot, cx: &mut App) {
+        cx.default_global::<Self>().snapshots.insert(tag, snapshot);
+    }
+
+    /// Forgets the snapshot published under the given tag, so the next element
+    /// rendered with it doesn't animate.
+    pub fn remove(tag: &ElementId, cx: &mut App) {
+        if let Some(registry) = cx.try_global::<Self>()
+            && registry.snapshots.contains_key(tag)
+        {
+            cx.global_mut::<Self>().snapshots.remove(tag);
+        }
+    }
+}
+
+/// Wraps `child` in a [`SharedElement`] with the given tag.
+pub fn shared_element(tag: impl Into<ElementId>, child: impl IntoElement) -> SharedElement {
+    SharedElement {
+        tag: tag.into(),
+        child: child.into_any_element(),
+        corner_radii: Corners::default(),
+        background: Rgba::default(),
+        duration: Duration::from_millis(300),
+        easing: Rc::new(linear),
+    }
+}
+
+/// An element that morphs from the last element rendered with the same tag,
+/// such as a thumbnail that grows into a detail panel.
+///
+/// A shared element paints its background, with rounded corners, behind its
+/// child. When it is first rendered and another shared element has published a
+/// [`SharedElementSnapshot`] under the same tag, it animates its bounds, corner
+/// radii and background from that snapshot to its own, using a
+/// [`Transition<SharedElementSnapshot>`](crate::Transition). As with a
+/// [`LayoutTransition`](crate::LayoutTransition), the child is offset and
+/// clipped to the animated bounds.
+///
+/// The tag identifies the element across views, and is also used as the
+/// element's ID, so it must be unique among its siblings.
+///
+/// # Example
+///
+/// ```ignore
+/// // In the gallery view.
+/// shared_element(("photo", photo.id), img(photo.thumbnail.clone()))
+///     .corner_radii(Corners::all(px(8.)))
+///     .background(rgb(0x222222))
+///
+/// // In the detail view.
+/// shared_element(("photo", photo.id), img(photo.full.clone()))
+///     .background(rgb(0x000000))
+///     .with_easing(ease_in_out)
+/// ```
+pub struct SharedElement {
+    tag: ElementId,
+    child: AnyElement,
+    corner_radii: Corners<Pixels>,
+    background: Rgba,
+    duration: Duration,
+    easing: Rc<dyn Fn(f32) -> f32>,
+}
+
+impl SharedElement {
+    /// Sets the radii of the element's corners.
+    pub fn corner_radii(mut self, corner_radii: impl Into<Corners<Pixels>>) -> Self {
+        self.corner_radii = corner_radii.into();
+        self
+    }
+
+    /// Sets the background color painted behind the child.
+    pub fn background(mut self, background: impl Into<Rgba>) -> Self {
+        self.background = background.into();
+        self
+    }
+
+    /// Sets how long it takes to morph from the previous element. Defaults to 300ms.
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Sets the easing function used when morphing from the previous element.
+    pub fn with_easing(mut self, easing: impl Fn(f32) -> f32 + 'static) -> Self {
+        self.easing = Rc::new(easing);
+        self
+    }
+}
+
+impl IntoElement for SharedElement {
+    type Element = Self;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+impl Element for SharedElement {
+    type RequestLayoutState = ();
+    type PrepaintState = SharedElementSnapshot;
+
+    fn id(&self) -> Option<ElementId> {
+        Some(self.tag.clone())
+    }
+
+    fn source_location(&self) -> Option<&'static panic::Location<'static>> {
+        None
+    }
+
+    fn request_layout(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _inspector_id: Option<&InspectorElementId>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> (LayoutId, Self::RequestLayoutState) {
+        (self.child.request_layout(window, cx), ())
+    }
+
+    fn prepaint(
+        &mut self,
+        id: Option<&GlobalElementId>,
+        _inspector_id: Option<&InspectorElementId>,
+        bounds: Bounds<Pixels>,
+        _request_layout: &mut Self::RequestLayoutState,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Self::PrepaintState {
+        let target = SharedElementSnapshot {
+            bounds,
+            corner_radii: self.corner_radii,
+            background: self.background,
+        };
+
+        let state = window.with_element_state(
+            id.unwrap(),
+            |state: Option<Entity<TransitionState<SharedElementSnapshot>>>, _window| {
+                let state = state.unwrap_or_else(|| {
+                    let initial = SharedElementRegistry::get(&self.tag, cx).unwrap_or(target);
+                    cx.new(|_cx| TransitionState::new(initial))
+                });
+                (state.clone(), state)
+            },
+        );
+
+        let easing = self.easing.clone();
+        let transition =
+            Transition::new(state, self.duration).with_easing(move |delta| easing(delta));
+
+        transition.update(cx, |goal, _cx| *goal = target);
+        let snapshot = *transition.evaluate(window, cx);
+
+        SharedElementRegistry::publish(self.tag.clone(), snapshot, cx);
+
+        window.with_element_offset(snapshot.bounds.origin - bounds.origin, |window| {
+            self.child.prepaint(window, cx);
+        });
+
+        snapshot
+    }
+
+    fn paint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _inspector_id: Option<&InspectorElementId>,
+        _bounds: Bounds<Pixels>,
+        _request_layout: &mut Self::RequestLayoutState,
+        snapshot: &mut Self::PrepaintState,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        window.paint_quad(
+            fill(snapshot.bounds, snapshot.background).corner_radii(snapshot.corner_radii),
+        );
+
+        let mask = ContentMask {
+            bounds: snapshot.bounds,
+        };
+
+        window.with_content_mask(Some(mask), |window| self.child.paint(window, cx));
+    }
+}
+
+#[cfg(all(test, feature = "test-support"))]
+mod tests {
+    use super::*;
+    use gpui::{
+        Context, Render, Styled, TestAppContext, VisualTestContext, div, point, prelude::*, px,
+        rgb, size,
+    };
+
+    struct TestView {
+        detail: bool,
+    }
+
+    impl Render for TestView {
+        fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+            let (offset, width, radius, color) = if self.detail {
+                (px(100.), px(80.), px(0.), 0xFFFFFF)
+            } else {
+                (px(0.), px(20.), px(8.), 0x000000)
+            };
+
+            // A different parent ID makes the detail element a new element.
+            let parent = if self.detail { "detail" } else { "gallery" };
+
+            div()
+                .id(parent)
+                .flex()
+                .child(div().w(offset).h(px(10.)))
+                .child(
+                    shared_element("photo", div().w(width).h(px(20.)))
+                        .corner_radii(Corners::all(radius))
+                        .background(rgb(color))
+                        .duration(Duration::from_secs(60)),
+                )
+        }
+    }
+
+    fn snapshot(cx: &mut VisualTestContext) -> SharedElementSnapshot {
+        cx.update(|_window, cx| SharedElementRegistry::get(&"photo".into(), cx).unwrap())
+    }
+
+    #[gpui::test]
+    fn test_shared_element_publishes_snapshot(cx: &mut TestAppContext) {
+        let (_view, cx) = cx.add_window_view(|_window, _cx| TestView { detail: false });
+        cx.run_until_parked();
+
+        assert_eq!(
+            snapshot(cx),
+            SharedElementSnapshot {
+                bounds: Bounds {
+                    origin: point(px(0.), px(0.)),
+                    size: size(px(20.), px(20.)),
+                },
+                corner_radii: Corners::all(px(8.)),
+                background: rgb(0x000000),
+            }
+        );
+    }
+
+    #[gpui::test]
+    fn test_shared_element_morphs_from_previous_element(cx: &mut TestAppContext) {
+        let (view, cx) = cx.add_window_view(|_window, _cx| TestView { detail: false });
+        cx.run_until_parked();
+
+        view.update(cx, |view, cx| {
+            view.detail = true;
+            cx.notify();
+        });
+        cx.run_until_parked();
+
+        // The detail element starts out looking like the gallery element.
+        let morphing = snapshot(cx);
+        assert!(morphing.bounds.origin.x < px(1.));
+        assert!(morphing.bounds.size.width < px(21.));
+        assert!(morphing.corner_radii.top_left > px(7.9));
+        assert!(morphing.background.r < 0.01);
+    }
+
+    #[gpui::test]
+    fn test_shared_element_registry_remove(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let tag: ElementId = "photo".into();
+            let snapshot = SharedElementSnapshot {
+                bounds: Bounds::default(),
+                corner_radii: Corners::default(),
+                background: Rgba::default(),
+            };
+
+            SharedElementRegistry::publish(tag.clone(), snapshot, cx);
+            assert_eq!(SharedElementRegistry::get(&tag, cx), Some(snapshot));
+
+            SharedElementRegistry::remove(&tag, cx);
+            assert_eq!(SharedElementRegistry::get(&tag, cx), None);
+        });
+    }
+}
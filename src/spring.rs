@@ -0,0 +1,610 @@
+//! Physics-based transitions driven by a damped spring.
+
+use std::{
+    borrow::BorrowMut,
+    cell::{Ref, RefCell},
+    rc::Rc,
+    time::Instant,
+};
+
+use gpui::{App, Context, Entity, EntityId, Window};
+
+use crate::{Clock, Components, MotionSettings, SystemClock};
+
+/// The length of a single integration step, in seconds.
+pub(crate) const STEP_SECS: f32 = 1. / 240.;
+
+/// The maximum number of integration steps taken per evaluation (ten seconds of
+/// simulated time). This bounds the work done after a long pause between frames.
+pub(crate) const MAX_STEPS: usize = 2400;
+
+/// Estimates how long (in seconds) a spring takes to settle from a displacement
+/// of 1 to within 0.001 of its goal, from the decay rate of its envelope.
+pub(crate) fn settle_secs(stiffness: f32, damping: f32, mass: f32) -> f32 {
+    let natural_frequency = (stiffness / mass).sqrt();
+    let damping_ratio = damping / (2. * (stiffness * mass).sqrt());
+
+    let decay_rate = if damping_ratio < 1. {
+        damping_ratio * natural_frequency
+    } else {
+        natural_frequency * (damping_ratio - (damping_ratio * damping_ratio - 1.).sqrt())
+    };
+
+    1000_f32.ln() / decay_rate
+}
+
+/// Internal state container for a [`SpringTransition`].
+///
+/// This struct holds the spring's goal along with the position and velocity of
+/// every component of the animated value. Because the velocity lives in the
+/// state, it is preserved when the goal changes mid-flight.
+///
+/// You typically don't need to interact with this type directly. It's created
+/// and managed by [`SpringTransition`] and the
+/// [`WindowUseTransition`](crate::WindowUseTransition) methods.
+///
+/// # Type Parameter
+///
+/// * `T` - The type of value being animated. Must implement [`Components`],
+///   [`Clone`], and [`PartialEq`].
+#[derive(Clone)]
+pub struct SpringState<T: Components + Clone + PartialEq + 'static> {
+    pub(crate) initial_goal: T,
+    pub(crate) goal: T,
+    pub(crate) position: Vec<f32>,
+    pub(crate) velocity: Vec<f32>,
+    /// When the spring was last integrated. `None` while the spring is at rest.
+    pub(crate) last_stepped_at: Option<Instant>,
+    pub(crate) clock: Rc<dyn Clock>,
+}
+
+impl<T: Components + Clone + PartialEq + 'static> SpringState<T> {
+    /// Creates a new spring state at rest on the given initial goal.
+    pub fn new(initial_goal: T) -> Self {
+        let position = initial_goal.to_components();
+
+        Self {
+            velocity: vec![0.; position.len()],
+            position,
+            goal: initial_goal.clone(),
+            initial_goal,
+            last_stepped_at: None,
+            clock: Rc::new(SystemClock),
+        }
+    }
+
+    /// Sets the clock used to read the current time.
+    ///
+    /// Defaults to [`SystemClock`].
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Rc::new(clock);
+        self
+    }
+
+    fn settle(&mut self, goal: Vec<f32>) {
+        self.velocity = vec![0.; goal.len()];
+        self.position = goal;
+        self.last_stepped_at = None;
+    }
+}
+
+/// An animated transition toward a goal value, driven by a damped spring.
+///
+/// Unlike [`Transition`](crate::Transition), a `SpringTransition` has no fixed
+/// duration or easing. Each component of the value is pulled toward the goal by a
+/// spring and slowed down by damping. When the goal changes mid-flight, the
+/// current velocity is carried over, so retargeting never produces a visible kink.
+///
+/// # Type Parameters
+///
+/// * `T` - The type of value being animated. Must implement [`Components`],
+///   [`Clone`], and [`PartialEq`].
+///
+/// # Example
+///
+/// ```ignore
+/// let offset = window
+///     .use_keyed_spring("drawer", cx, |_, _| px(0.))
+///     .with_stiffness(300.)
+///     .with_damping(20.);
+///
+/// // Get the current value of the spring
+/// let value = offset.evaluate(window, cx);
+///
+/// // Update the goal
+/// offset.update(cx, |val, cx| {
+///     *val = px(240.);
+///     cx.notify();
+/// });
+/// ```
+#[derive(Clone)]
+pub struct SpringTransition<T: Components + Clone + PartialEq + 'static> {
+    /// How strongly the spring pulls toward the goal.
+    stiffness: f32,
+
+    /// How strongly the spring's motion is resisted.
+    damping: f32,
+
+    /// The mass attached to the spring. Heavier springs move more sluggishly.
+    mass: f32,
+
+    /// The distance from the goal below which a component may come to rest.
+    rest_displacement: f32,
+
+    /// The speed (in units per second) below which a component may come to rest.
+    rest_velocity: f32,
+
+    /// Whether the spring ignores the reduced motion of the [`MotionSettings`].
+    essential: bool,
+
+    state: Entity<SpringState<T>>,
+
+    /// A cached version of the spring's value.
+    cached_value: RefCell<Option<T>>,
+}
+
+impl<T: Components + Clone + PartialEq + 'static> SpringTransition<T> {
+    /// Create a new spring transition using the specified state.
+    ///
+    /// The spring starts with a stiffness of 170, a damping of 26 and a mass of 1,
+    /// which settles quickly without overshooting.
+    pub fn new(state: Entity<SpringState<T>>) -> Self {
+        Self {
+            stiffness: 170.,
+            damping: 26.,
+            mass: 1.,
+            rest_displacement: 0.001,
+            rest_velocity: 0.001,
+            essential: false,
+            state,
+            cached_value: RefCell::new(None),
+        }
+    }
+
+    /// Sets how strongly the spring pulls toward the goal.
+    pub fn with_stiffness(mut self, stiffness: f32) -> Self {
+        self.stiffness = stiffness;
+        self
+    }
+
+    /// Sets how strongly the spring's motion is resisted.
+    ///
+    /// Lower values make the spring oscillate around its goal before settling.
+    pub fn with_damping(mut self, damping: f32) -> Self {
+        self.damping = damping;
+        self
+    }
+
+    /// Sets the mass attached to the spring.
+    pub fn with_mass(mut self, mass: f32) -> Self {
+        self.mass = mass;
+        self
+    }
+
+    /// Sets the thresholds below which the spring snaps to its goal and stops.
+    ///
+    /// The spring comes to rest once every component is within `displacement` of
+    /// the goal and is moving slower than `velocity` units per second.
+    pub fn with_rest_thresholds(mut self, displacement: f32, velocity: f32) -> Self {
+        self.rest_displacement = displacement;
+        self.rest_velocity = velocity;
+        self
+    }
+
+    /// Sets whether the spring is essential and should move even when the
+    /// app-wide [`MotionSettings`] reduce motion. The time scale still applies.
+    pub fn essential(mut self, essential: bool) -> Self {
+        self.essential = essential;
+        self
+    }
+
+    fn has_settled(&self, state: &SpringState<T>, goal: &[f32]) -> bool {
+        state
+            .position
+            .iter()
+            .zip(&state.velocity)
+            .zip(goal)
+            .all(|((position, velocity), goal)| {
+                (position - goal).abs() <= self.rest_displacement
+                    && velocity.abs() <= self.rest_velocity
+            })
+    }
+
+    /// Integrates the spring up to the current time, at the rate given by the
+    /// motion settings. Returns if the spring is still moving.
+    fn step(&self, state: &mut SpringState<T>, motion: &MotionSettings) -> bool {
+        let Some(last_stepped_at) = state.last_stepped_at else {
+            return false;
+        };
+
+        let now = state.clock.now();
+        let goal = state.goal.to_components();
+
+        if state.position.len() != goal.len() {
+            state.position.resize(goal.len(), 0.);
+        }
+        if state.velocity.len() != goal.len() {
+            state.velocity.resize(goal.len(), 0.);
+        }
+
+        let natural_secs = settle_secs(self.stiffness, self.damping, self.mass);
+        let Some(rate) = motion.playback_rate(natural_secs, self.essential) else {
+            state.settle(goal);
+            return false;
+        };
+
+        let mut remaining_secs =
+            now.saturating_duration_since(last_stepped_at).as_secs_f32() * rate;
+        let mut steps = 0;
+
+        while remaining_secs > 0. && steps < MAX_STEPS {
+            let dt = remaining_secs.min(STEP_SECS);
+
+            for ((position, velocity), goal) in state
+                .position
+                .iter_mut()
+                .zip(state.velocity.iter_mut())
+                .zip(&goal)
+            {
+                let spring_force = -self.stiffness * (*position - goal);
+                let damping_force = -self.damping * *velocity;
+
+                *velocity += (spring_force + damping_force) / self.mass * dt;
+                *position += *velocity * dt;
+            }
+
+            remaining_secs -= dt;
+            steps += 1;
+
+            if self.has_settled(state, &goal) {
+                state.settle(goal);
+                return false;
+            }
+        }
+
+        state.last_stepped_at = Some(now);
+        true
+    }
+
+    /// Evaluates the value of the spring without using the cache.
+    /// Returns if the spring is still moving (bool) and the evaluated value (T).
+    fn raw_evaluate(&self, cx: &mut App) -> (bool, T) {
+        let motion = MotionSettings::get(cx);
+        let mut state_entity = self.state.as_mut(cx);
+        let state: &mut SpringState<T> = state_entity.borrow_mut();
+
+        if self.step(state, &motion) {
+            // A frozen spring doesn't move, so it doesn't need animation frames.
+            let moving = motion.time_scale() > 0.;
+            (moving, T::from_components(&state.position))
+        } else {
+            (false, state.goal.clone())
+        }
+    }
+
+    /// Evaluates and returns the current value of the spring.
+    ///
+    /// This method integrates the spring up to the current time and caches the
+    /// result. If the spring is still moving, it automatically requests an
+    /// animation frame to continue the animation.
+    ///
+    /// The returned value is cached for the duration of the current frame to avoid
+    /// redundant calculations when called multiple times.
+    pub fn evaluate(&self, window: &mut Window, cx: &mut App) -> Ref<'_, T> {
+        if self.cached_value.borrow().is_none() {
+            self.state.read(cx).clock.begin_frame(window);
+
+            let (in_progress, evaluated_value) = self.raw_evaluate(cx);
+
+            if in_progress {
+                window.request_animation_frame();
+            }
+
+            *self.cached_value.borrow_mut() = Some(evaluated_value);
+        }
+
+        Ref::map(self.cached_value.borrow(), |opt| opt.as_ref().unwrap())
+    }
+
+    /// Reads the goal of the spring.
+    pub fn read_goal<'b>(&'b self, cx: &'b mut App) -> &'b T {
+        &self.state.read(cx).goal
+    }
+
+    /// Reads the current value of the cached spring, if it exists.
+    pub fn read_cache(&self) -> Ref<'_, Option<T>> {
+        self.cached_value.borrow()
+    }
+
+    /// Returns whether the spring has settled on its goal.
+    pub fn is_at_rest(&self, cx: &App) -> bool {
+        self.state.read(cx).last_stepped_at.is_none()
+    }
+
+    /// Updates the goal value for the spring.
+    ///
+    /// The provided closure receives a mutable reference to the current goal value
+    /// and can modify it. The spring keeps its current position and velocity, and
+    /// starts pulling toward the new goal from there.
+    ///
+    /// Returns `true` if the goal was actually updated (i.e., the new value differs
+    /// from the previous goal), `false` otherwise.
+    ///
+    /// Note: This method does not automatically notify GPUI of changes. You should
+    /// call `cx.notify()` within the closure if you want to trigger a re-render.
+    pub fn update<R>(
+        &self,
+        cx: &mut App,
+        update: impl FnOnce(&mut T, &mut Context<SpringState<T>>) -> R,
+    ) -> bool {
+        let motion = MotionSettings::get(cx);
+        let mut was_updated = false;
+
+        self.state.update(cx, |state, cx| {
+            // Bring the spring up to date so the time elapsed so far is
+            // integrated against the previous goal.
+            self.step(state, &motion);
+
+            let last_goal = state.goal.clone();
+
+            update(&mut state.goal, cx);
+
+            if state.goal == last_goal {
+                return;
+            }
+
+            if state.last_stepped_at.is_none() {
+                state.last_stepped_at = Some(state.clock.now());
+            }
+
+            was_updated = true;
+        });
+
+        was_updated
+    }
+
+    /// Replaces the clock that this spring's state reads the current time from.
+    pub fn set_clock(&self, cx: &mut App, clock: impl Clock + 'static) {
+        self.state.update(cx, |state, _cx| {
+            state.clock = Rc::new(clock);
+        });
+    }
+
+    /// Returns the entity ID associated with this spring's state.
+    pub fn entity_id(&self) -> EntityId {
+        self.state.entity_id()
+    }
+
+    /// Resets the spring to rest on its initial goal.
+    ///
+    /// The cache is also cleared.
+    pub fn reset(&self, cx: &mut App) {
+        self.state.update(cx, |state, _cx| {
+            state.goal = state.initial_goal.clone();
+            state.settle(state.initial_goal.to_components());
+        });
+        *self.cached_value.borrow_mut() = None;
+    }
+}
+
+#[cfg(all(test, feature = "test-support"))]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{ManualClock, ReducedMotion};
+    use gpui::{AppContext, Point, TestAppContext};
+
+    fn create_spring<T: Components + Clone + PartialEq + 'static>(
+        cx: &mut App,
+        clock: &ManualClock,
+        initial: T,
+    ) -> SpringTransition<T> {
+        let state = cx.new(|_| SpringState::new(initial).with_clock(clock.clone()));
+        SpringTransition::new(state)
+    }
+
+    #[test]
+    fn test_new_spring_state_is_at_rest() {
+        let state = SpringState::new(Point { x: 1.0_f32, y: 2.0 });
+
+        assert_eq!(state.position, vec![1.0, 2.0]);
+        assert_eq!(state.velocity, vec![0.0, 0.0]);
+        assert!(state.last_stepped_at.is_none());
+    }
+
+    #[gpui::test]
+    fn test_spring_starts_at_rest(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let clock = ManualClock::new();
+            let spring = create_spring(cx, &clock, 10.0_f32);
+
+            let (in_progress, value) = spring.raw_evaluate(cx);
+            assert!(!in_progress);
+            assert_eq!(value, 10.0);
+            assert!(spring.is_at_rest(cx));
+        });
+    }
+
+    #[gpui::test]
+    fn test_spring_update_returns_false_on_no_change(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let clock = ManualClock::new();
+            let spring = create_spring(cx, &clock, 10.0_f32);
+
+            assert!(!spring.update(cx, |val, _cx| *val = 10.0));
+            assert!(spring.is_at_rest(cx));
+        });
+    }
+
+    #[gpui::test]
+    fn test_spring_moves_toward_goal(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let clock = ManualClock::new();
+            let spring = create_spring(cx, &clock, 0.0_f32);
+
+            assert!(spring.update(cx, |val, _cx| *val = 100.0));
+
+            clock.advance(Duration::from_millis(50));
+            let (in_progress, first) = spring.raw_evaluate(cx);
+            assert!(in_progress);
+            assert!(first > 0.0 && first < 100.0);
+
+            clock.advance(Duration::from_millis(50));
+            let (_, second) = spring.raw_evaluate(cx);
+            assert!(second > first);
+        });
+    }
+
+    #[gpui::test]
+    fn test_spring_settles_on_goal(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let clock = ManualClock::new();
+            let spring = create_spring(cx, &clock, Point { x: 0.0_f32, y: 0.0 });
+
+            spring.update(cx, |val, _cx| *val = Point { x: 100.0, y: -50.0 });
+
+            clock.advance(Duration::from_secs(5));
+            let (in_progress, value) = spring.raw_evaluate(cx);
+            assert!(!in_progress);
+            assert_eq!(value, Point { x: 100.0, y: -50.0 });
+            assert!(spring.is_at_rest(cx));
+        });
+    }
+
+    #[gpui::test]
+    fn test_underdamped_spring_overshoots(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let clock = ManualClock::new();
+            let spring = create_spring(cx, &clock, 0.0_f32).with_damping(5.);
+
+            spring.update(cx, |val, _cx| *val = 100.0);
+
+            let mut max_value = 0.0_f32;
+            for _ in 0..100 {
+                clock.advance(Duration::from_millis(10));
+                max_value = max_value.max(spring.raw_evaluate(cx).1);
+            }
+
+            assert!(max_value > 100.0);
+        });
+    }
+
+    #[gpui::test]
+    fn test_spring_preserves_velocity_when_retargeted(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let clock = ManualClock::new();
+            let spring = create_spring(cx, &clock, 0.0_f32);
+
+            spring.update(cx, |val, _cx| *val = 100.0);
+
+            clock.advance(Duration::from_millis(100));
+            spring.raw_evaluate(cx);
+            let velocity_before = spring.state.read(cx).velocity.clone();
+            let position_before = spring.state.read(cx).position.clone();
+            assert!(velocity_before[0] > 0.0);
+
+            spring.update(cx, |val, _cx| *val = -100.0);
+
+            assert_eq!(spring.state.read(cx).velocity, velocity_before);
+            assert_eq!(spring.state.read(cx).position, position_before);
+
+            // The spring keeps moving forward for a moment before turning around.
+            clock.advance(Duration::from_millis(5));
+            let (_, value) = spring.raw_evaluate(cx);
+            assert!(value > position_before[0]);
+        });
+    }
+
+    #[test]
+    fn test_settle_secs() {
+        // The default spring is close to critically damped.
+        let secs = settle_secs(170., 26., 1.);
+        assert!(secs > 0.4 && secs < 0.7);
+
+        // A lightly damped spring rings for longer.
+        assert!(settle_secs(170., 5., 1.) > secs);
+    }
+
+    #[gpui::test]
+    fn test_spring_follows_motion_settings(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let clock = ManualClock::new();
+            let spring = create_spring(cx, &clock, 0.0_f32);
+            let reference = create_spring(cx, &clock, 0.0_f32);
+
+            MotionSettings::set(MotionSettings::default().with_time_scale(0.5), cx);
+            spring.update(cx, |val, _cx| *val = 100.0);
+            MotionSettings::set(MotionSettings::default(), cx);
+            reference.update(cx, |val, _cx| *val = 100.0);
+
+            // At half speed, the spring covers in 100ms what it would in 50ms.
+            clock.advance(Duration::from_millis(50));
+            let (_, expected) = reference.raw_evaluate(cx);
+            MotionSettings::set(MotionSettings::default().with_time_scale(0.5), cx);
+            clock.advance(Duration::from_millis(50));
+            let (_, value) = spring.raw_evaluate(cx);
+            assert!((value - expected).abs() < 0.01);
+
+            // A frozen spring stays where it is and doesn't ask for frames.
+            MotionSettings::set(MotionSettings::default().with_time_scale(0.), cx);
+            clock.advance(Duration::from_millis(50));
+            assert_eq!(spring.raw_evaluate(cx), (false, value));
+
+            MotionSettings::set(MotionSettings::default(), cx);
+            clock.advance(Duration::from_millis(10));
+            assert!(spring.raw_evaluate(cx).0);
+        });
+    }
+
+    #[gpui::test]
+    fn test_spring_reduced_motion(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let clock = ManualClock::new();
+            let spring = create_spring(cx, &clock, 0.0_f32);
+            let essential = create_spring(cx, &clock, 0.0_f32).essential(true);
+
+            MotionSettings::set(
+                MotionSettings::default().with_reduced_motion(Some(ReducedMotion::Snap)),
+                cx,
+            );
+
+            spring.update(cx, |val, _cx| *val = 100.0);
+            essential.update(cx, |val, _cx| *val = 100.0);
+
+            clock.advance(Duration::from_millis(10));
+            assert_eq!(spring.raw_evaluate(cx), (false, 100.0));
+            assert!(essential.raw_evaluate(cx).0);
+
+            // Shortened springs speed up to come within 1% of their goal in time.
+            MotionSettings::set(
+                MotionSettings::default()
+                    .with_reduced_motion(Some(ReducedMotion::Shorten(Duration::from_millis(100)))),
+                cx,
+            );
+
+            spring.update(cx, |val, _cx| *val = 0.0);
+            clock.advance(Duration::from_millis(100));
+            assert!(spring.raw_evaluate(cx).1.abs() < 1.);
+        });
+    }
+
+    #[gpui::test]
+    fn test_spring_reset(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let clock = ManualClock::new();
+            let spring = create_spring(cx, &clock, 5.0_f32);
+
+            spring.update(cx, |val, _cx| *val = 50.0);
+            clock.advance(Duration::from_millis(100));
+            spring.raw_evaluate(cx);
+
+            spring.reset(cx);
+
+            assert_eq!(*spring.read_goal(cx), 5.0);
+            assert!(spring.is_at_rest(cx));
+            assert!(spring.read_cache().is_none());
+            assert_eq!(spring.raw_evaluate(cx).1, 5.0);
+        });
+    }
+}
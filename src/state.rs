@@ -1,14 +1,19 @@
 //! Internal state management for transitions.
 
-use std::time::Instant;
+use std::{
+    collections::VecDeque,
+    rc::Rc,
+    time::{Duration, Instant},
+};
 
-use crate::Lerp;
+use crate::{Clock, Lerp, SystemClock, motion::scale_elapsed};
 
 /// Internal state container for a [`Transition`](crate::Transition).
 ///
 /// This struct holds the data necessary to track a transition's progress,
 /// including the start and end goals, timing information, and the last
-/// computed delta value.
+/// computed delta value. It also owns the [`Clock`] that the transition reads
+/// the current time from.
 ///
 /// You typically don't need to interact with this type directly. It's created
 /// and managed by [`Transition`](crate::Transition) and the
@@ -20,11 +25,36 @@ use crate::Lerp;
 ///   and [`PartialEq`].
 #[derive(Clone)]
 pub struct TransitionState<T: Lerp + Clone + PartialEq + 'static> {
+    /// When the current stretch of playback started. Set when the goal changes,
+    /// and when the transition is resumed or seeked.
     pub(crate) goal_last_updated_at: Option<Instant>,
+    /// The elapsed time accumulated before `goal_last_updated_at`, by playing
+    /// before a pause or by seeking.
+    pub(crate) elapsed_offset: Duration,
+    /// Whether playback is paused, which freezes the elapsed time at `elapsed_offset`.
+    pub(crate) paused: bool,
+    /// The rate at which the elapsed time advances after `goal_last_updated_at`,
+    /// from the app-wide [`MotionSettings`](crate::MotionSettings).
+    pub(crate) time_scale: f32,
+    /// Whether the start and end goals were swapped to play the animation
+    /// backwards, which retraces the easing curve instead of replaying it.
+    pub(crate) reversed: bool,
+    /// The duration (in seconds) of the current animation, if it was scaled
+    /// because it interrupted another one.
+    pub(crate) scaled_duration_secs: Option<f32>,
     pub(crate) initial_goal: T,
     pub(crate) start_goal: T,
     pub(crate) end_goal: T,
     pub(crate) last_delta: f32,
+    /// Whether the current goal has been set but not yet reported as reached.
+    pub(crate) completion_pending: bool,
+    /// Incremented every time the goal changes or the transition is reset.
+    pub(crate) generation: usize,
+    /// The generation whose goal was most recently reached.
+    pub(crate) completed_generation: Option<usize>,
+    /// Goals waiting for the current goal to be reached, oldest first.
+    pub(crate) queued_goals: VecDeque<T>,
+    pub(crate) clock: Rc<dyn Clock>,
 }
 
 impl<T: Lerp + Clone + PartialEq + 'static> TransitionState<T> {
@@ -36,12 +66,65 @@ impl<T: Lerp + Clone + PartialEq + 'static> TransitionState<T> {
     pub fn new(initial_goal: T) -> Self {
         Self {
             goal_last_updated_at: None,
+            elapsed_offset: Duration::ZERO,
+            paused: false,
+            time_scale: 1.,
+            reversed: false,
+            scaled_duration_secs: None,
             initial_goal: initial_goal.clone(),
             start_goal: initial_goal.clone(),
             end_goal: initial_goal,
             last_delta: 1.,
+            completion_pending: false,
+            generation: 0,
+            completed_generation: None,
+            queued_goals: VecDeque::new(),
+            clock: Rc::new(SystemClock),
         }
     }
+
+    /// Sets the clock used to read the current time.
+    ///
+    /// Defaults to [`SystemClock`]. Use a [`ManualClock`](crate::ManualClock) to step
+    /// the transition deterministically.
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Rc::new(clock);
+        self
+    }
+
+    /// Returns how long the transition has been playing towards its current goal,
+    /// including any delay and scaled by the time scale, or `None` if the goal
+    /// has never changed.
+    pub(crate) fn elapsed(&self) -> Option<Duration> {
+        let started_at = self.goal_last_updated_at?;
+
+        if self.paused {
+            return Some(self.elapsed_offset);
+        }
+
+        let since_started = self.clock.now().saturating_duration_since(started_at);
+        Some(
+            self.elapsed_offset
+                .saturating_add(scale_elapsed(since_started, self.time_scale)),
+        )
+    }
+
+    /// Changes the rate at which the elapsed time advances from now on, keeping
+    /// the time that has elapsed so far.
+    pub(crate) fn set_time_scale(&mut self, time_scale: f32) {
+        if self.time_scale == time_scale {
+            return;
+        }
+
+        if !self.paused
+            && let Some(elapsed) = self.elapsed()
+        {
+            self.elapsed_offset = elapsed;
+            self.goal_last_updated_at = Some(self.clock.now());
+        }
+
+        self.time_scale = time_scale;
+    }
 }
 
 #[cfg(all(test, feature = "test-support"))]
@@ -0,0 +1,235 @@
+//! Extension methods for transitioning the style properties of elements.
+
+use std::{rc::Rc, time::Duration};
+
+use gpui::{App, Edges, ElementId, Pixels, Rgba, Size, Styled, Window, linear};
+
+use crate::{Lerp, WindowUseTransition};
+
+/// The duration and easing used by the methods of [`StyledTransition`].
+///
+/// Can be created from a [`Duration`], in which case the easing is linear.
+#[derive(Clone)]
+pub struct StyleTransition {
+    duration: Duration,
+    easing: Rc<dyn Fn(f32) -> f32>,
+}
+
+impl StyleTransition {
+    /// Creates a new linear style transition with the given duration.
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            easing: Rc::new(linear),
+        }
+    }
+
+    /// Sets the easing function of this style transition.
+    pub fn with_easing(mut self, easing: impl Fn(f32) -> f32 + 'static) -> Self {
+        self.easing = Rc::new(easing);
+        self
+    }
+}
+
+impl From<Duration> for StyleTransition {
+    fn from(duration: Duration) -> Self {
+        Self::new(duration)
+    }
+}
+
+/// Extension methods for GPUI's [`Styled`] that animate a style property towards
+/// the given value whenever it changes, like CSS's `transition-property`.
+///
+/// Each method keeps a keyed [`Transition`](crate::Transition) for its property,
+/// keyed by the given ID and the property's name, so the same ID can be used for
+/// several properties of one element. The ID must be unique within the current
+/// element, like the key of [`use_keyed_transition`](WindowUseTransition::use_keyed_transition).
+/// The property takes the given value immediately when it is first rendered.
+///
+/// # Example
+///
+/// ```ignore
+/// let background = if self.selected { rgb(0x2A2730) } else { rgb(0x110F15) };
+///
+/// div()
+///     .transition_bg("card", background, Duration::from_millis(150), window, cx)
+///     .transition_opacity(
+///         "card",
+///         if self.selected { 1.0 } else { 0.8 },
+///         StyleTransition::new(Duration::from_millis(300)).with_easing(ease_in_out),
+///         window,
+///         cx,
+///     )
+/// ```
+pub trait StyledTransition: Styled + Sized {
+    /// Transitions the background color towards `color`.
+    fn transition_bg(
+        self,
+        id: impl Into<ElementId>,
+        color: impl Into<Rgba>,
+        transition: impl Into<StyleTransition>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Self {
+        let color = transition_property(id, "bg", color.into(), transition, window, cx);
+        self.bg(color)
+    }
+
+    /// Transitions the opacity towards `opacity`.
+    fn transition_opacity(
+        self,
+        id: impl Into<ElementId>,
+        opacity: f32,
+        transition: impl Into<StyleTransition>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Self {
+        let opacity = transition_property(id, "opacity", opacity, transition, window, cx);
+        self.opacity(opacity)
+    }
+
+    /// Transitions the width and height towards `size`.
+    fn transition_size(
+        self,
+        id: impl Into<ElementId>,
+        size: Size<Pixels>,
+        transition: impl Into<StyleTransition>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Self {
+        let size = transition_property(id, "size", size, transition, window, cx);
+        self.w(size.width).h(size.height)
+    }
+
+    /// Transitions the padding of every edge towards `padding`.
+    fn transition_padding(
+        self,
+        id: impl Into<ElementId>,
+        padding: Edges<Pixels>,
+        transition: impl Into<StyleTransition>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Self {
+        let padding = transition_property(id, "padding", padding, transition, window, cx);
+        self.pt(padding.top)
+            .pr(padding.right)
+            .pb(padding.bottom)
+            .pl(padding.left)
+    }
+}
+
+impl<E: Styled> StyledTransition for E {}
+
+/// Moves the transition for the given property towards `goal` and returns its
+/// current value.
+fn transition_property<T: Lerp + Clone + PartialEq + 'static>(
+    id: impl Into<ElementId>,
+    property: &'static str,
+    goal: T,
+    transition: impl Into<StyleTransition>,
+    window: &mut Window,
+    cx: &mut App,
+) -> T {
+    let StyleTransition { duration, easing } = transition.into();
+
+    let transition = window
+        .use_keyed_transition((id.into(), property), cx, duration, |_window, _cx| {
+            goal.clone()
+        })
+        .with_easing(move |delta| easing(delta));
+
+    transition.update(cx, |value, _cx| *value = goal);
+
+    transition.evaluate(window, cx).clone()
+}
+
+#[cfg(all(test, feature = "test-support"))]
+mod tests {
+    use super::*;
+    use gpui::{
+        Context, Entity, IntoElement, Render, TestAppContext, VisualTestContext, div, px, size,
+    };
+    use std::cell::Cell;
+
+    struct TestView {
+        opacity: f32,
+        width: Pixels,
+        duration: Duration,
+        rendered: Rc<Cell<(Option<f32>, Option<gpui::Length>)>>,
+    }
+
+    impl Render for TestView {
+        fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+            let mut element = div()
+                .transition_opacity("box", self.opacity, self.duration, window, cx)
+                .transition_size("box", size(self.width, px(10.)), self.duration, window, cx);
+
+            let style = element.style();
+            self.rendered.set((style.opacity, style.size.width));
+
+            element
+        }
+    }
+
+    fn add_view(
+        cx: &mut TestAppContext,
+        duration: Duration,
+    ) -> (Entity<TestView>, &mut VisualTestContext) {
+        cx.add_window_view(|_window, _cx| TestView {
+            opacity: 0.,
+            width: px(0.),
+            duration,
+            rendered: Rc::default(),
+        })
+    }
+
+    fn set_goals(view: &Entity<TestView>, cx: &mut VisualTestContext) {
+        view.update(cx, |view, cx| {
+            view.opacity = 1.;
+            view.width = px(100.);
+            cx.notify();
+        });
+        cx.run_until_parked();
+    }
+
+    fn rendered(view: &Entity<TestView>, cx: &mut VisualTestContext) -> (f32, Pixels) {
+        let (opacity, width) = view.read_with(cx, |view, _cx| view.rendered.get());
+
+        let Some(gpui::Length::Definite(gpui::DefiniteLength::Absolute(
+            gpui::AbsoluteLength::Pixels(width),
+        ))) = width
+        else {
+            panic!("expected an absolute width");
+        };
+
+        (opacity.unwrap(), width)
+    }
+
+    #[gpui::test]
+    fn test_styled_transition_starts_at_value(cx: &mut TestAppContext) {
+        let (view, cx) = add_view(cx, Duration::from_secs(60));
+        cx.run_until_parked();
+
+        assert_eq!(rendered(&view, cx), (0., px(0.)));
+    }
+
+    #[gpui::test]
+    fn test_styled_transition_animates_changes(cx: &mut TestAppContext) {
+        let (view, cx) = add_view(cx, Duration::from_secs(60));
+        cx.run_until_parked();
+        set_goals(&view, cx);
+
+        let (opacity, width) = rendered(&view, cx);
+        assert!(opacity < 0.01);
+        assert!(width < px(1.));
+    }
+
+    #[gpui::test]
+    fn test_styled_transition_without_duration(cx: &mut TestAppContext) {
+        let (view, cx) = add_view(cx, Duration::ZERO);
+        cx.run_until_parked();
+        set_goals(&view, cx);
+
+        assert_eq!(rendered(&view, cx), (1., px(100.)));
+    }
+}
@@ -0,0 +1,620 @@
+//! Timelines that choreograph several tweens on a single playhead.
+
+use std::{
+    cell::Cell,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use gpui::{App, Entity, EntityId, Window, linear};
+
+use crate::{Clock, Lerp, MotionSettings, SystemClock, motion::scale_elapsed};
+
+/// An animation from one value to another, to be placed on a [`Timeline`].
+#[derive(Clone)]
+pub struct Tween<T: Lerp + Clone + 'static> {
+    from: T,
+    to: T,
+    duration: Duration,
+    delay: Duration,
+    easing: Rc<dyn Fn(f32) -> f32>,
+}
+
+impl<T: Lerp + Clone + 'static> Tween<T> {
+    /// Creates a new linear tween from `from` to `to`, over the given duration.
+    pub fn new(from: T, to: T, duration: Duration) -> Self {
+        Self {
+            from,
+            to,
+            duration,
+            delay: Duration::ZERO,
+            easing: Rc::new(linear),
+        }
+    }
+
+    /// Sets the easing function of this tween.
+    pub fn with_easing(mut self, easing: impl Fn(f32) -> f32 + 'static) -> Self {
+        self.easing = Rc::new(easing);
+        self
+    }
+
+    /// Offsets the start of this tween by `delay`, relative to where it is placed
+    /// on the timeline.
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+}
+
+/// A [`Tween`] placed on a [`Timeline`], returned when the tween is added.
+///
+/// Its value is read with [`Timeline::evaluate`].
+#[derive(Clone)]
+pub struct Track<T: Lerp + Clone + 'static> {
+    tween: Tween<T>,
+    start: Duration,
+}
+
+impl<T: Lerp + Clone + 'static> Track<T> {
+    /// Returns when this track starts on the timeline.
+    pub fn start(&self) -> Duration {
+        self.start
+    }
+
+    /// Returns when this track ends on the timeline.
+    pub fn end(&self) -> Duration {
+        self.start + self.tween.duration
+    }
+
+    /// Returns the value of this track with the playhead at the given time.
+    fn value_at(&self, playhead: Duration) -> T {
+        let progress = if playhead >= self.end() {
+            1.
+        } else if playhead <= self.start {
+            0.
+        } else {
+            (playhead - self.start).as_secs_f32() / self.tween.duration.as_secs_f32()
+        };
+
+        self.tween
+            .from
+            .lerp(&self.tween.to, (self.tween.easing)(progress))
+    }
+}
+
+/// The direction in which a [`Timeline`]'s playhead moves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Playback {
+    Paused,
+    Forward,
+    Reverse,
+}
+
+/// State container for a [`Timeline`].
+///
+/// Holds the position of the playhead and the direction it's moving in. The
+/// tracks themselves are added to the timeline on every render, so they aren't
+/// part of the state.
+pub struct TimelineState {
+    /// The position of the playhead at `started_at`.
+    position: Duration,
+    started_at: Instant,
+    playback: Playback,
+    /// The speed at which the playhead moves, from the [`MotionSettings`].
+    rate: f32,
+    clock: Rc<dyn Clock>,
+}
+
+impl Default for TimelineState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TimelineState {
+    /// Creates a new, paused timeline state with the playhead at the start.
+    pub fn new() -> Self {
+        let clock = Rc::new(SystemClock);
+
+        Self {
+            position: Duration::ZERO,
+            started_at: clock.now(),
+            playback: Playback::Paused,
+            rate: 1.,
+            clock,
+        }
+    }
+
+    /// Sets the clock used to read the current time.
+    ///
+    /// Defaults to [`SystemClock`]. Use a [`ManualClock`](crate::ManualClock) to step
+    /// the timeline deterministically.
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Rc::new(clock);
+        self.started_at = self.clock.now();
+        self
+    }
+
+    /// Returns the position of the playhead, clamped to the given duration.
+    fn playhead(&self, duration: Duration) -> Duration {
+        let elapsed = scale_elapsed(
+            self.clock.now().saturating_duration_since(self.started_at),
+            self.rate,
+        );
+
+        let position = match self.playback {
+            Playback::Paused => self.position,
+            Playback::Forward => self.position.saturating_add(elapsed),
+            Playback::Reverse => self.position.saturating_sub(elapsed),
+        };
+
+        position.min(duration)
+    }
+
+    /// Returns whether a playhead at `playhead` still has somewhere to move.
+    fn is_moving(&self, playhead: Duration, duration: Duration) -> bool {
+        match self.playback {
+            Playback::Paused => false,
+            Playback::Forward => playhead < duration,
+            Playback::Reverse => playhead > Duration::ZERO,
+        }
+    }
+
+    /// Returns whether the playhead was playing and has reached the end it was
+    /// moving towards.
+    fn has_finished(&self, duration: Duration) -> bool {
+        self.playback != Playback::Paused && !self.is_moving(self.playhead(duration), duration)
+    }
+
+    /// Stops a playhead that has reached its end, so that it stays there even if
+    /// tracks are added and the duration grows.
+    fn settle(&mut self, duration: Duration) {
+        if self.has_finished(duration) {
+            let position = self.playhead(duration);
+            self.restart(position, Playback::Paused);
+        }
+    }
+
+    /// Changes the speed at which the playhead moves from now on, keeping it where
+    /// it is.
+    fn set_rate(&mut self, rate: f32, duration: Duration) {
+        if self.rate == rate {
+            return;
+        }
+
+        let position = self.playhead(duration);
+        self.restart(position, self.playback);
+        self.rate = rate;
+    }
+
+    /// Moves the playhead to `position` and plays it in the given direction from
+    /// there.
+    fn restart(&mut self, position: Duration, playback: Playback) {
+        self.position = position;
+        self.started_at = self.clock.now();
+        self.playback = playback;
+    }
+}
+
+/// A timeline that plays several [`Tween`]s from a single playhead, in sequence
+/// and in parallel, like a GSAP timeline.
+///
+/// Tweens are added to the timeline on every render, and each returns a
+/// [`Track`] whose value is read with [`evaluate`](Self::evaluate).
+/// [`then`](Self::then) places a tween after everything added so far,
+/// [`alongside`](Self::alongside) starts it together with the previous tween, and
+/// [`at`](Self::at) places it at a fixed time. A tween's own
+/// [`with_delay`](Tween::with_delay) offsets it from that position.
+///
+/// The timeline starts paused at the beginning. It can be played forwards with
+/// [`play`](Self::play), backwards with [`reverse`](Self::reverse), and its
+/// playhead moved with [`seek`](Self::seek). Because the tracks are recreated on
+/// every render, playback controls should be called on a timeline that already
+/// had its tracks added, so it knows its duration.
+///
+/// # Example
+///
+/// ```ignore
+/// let mut timeline = window.use_keyed_timeline("open-panel", cx);
+///
+/// let panel_x = timeline.then(
+///     Tween::new(px(-320.), px(0.), Duration::from_millis(300))
+///         .with_easing(easing::ease_out_cubic),
+/// );
+/// let backdrop = timeline.alongside(Tween::new(0.0, 0.5, Duration::from_millis(300)));
+/// let contents = timeline.then(Tween::new(0.0, 1.0, Duration::from_millis(200)));
+///
+/// let panel_x = timeline.evaluate(&panel_x, window, cx);
+/// let backdrop = timeline.evaluate(&backdrop, window, cx);
+/// let contents = timeline.evaluate(&contents, window, cx);
+///
+/// // Later, in an event handler.
+/// timeline.play(cx);
+/// ```
+#[derive(Clone)]
+pub struct Timeline {
+    /// When the last track ends.
+    duration: Duration,
+
+    /// Where the most recently added tween was placed, before its delay.
+    previous_start: Duration,
+
+    /// Whether the timeline ignores the reduced motion of the [`MotionSettings`].
+    essential: bool,
+
+    state: Entity<TimelineState>,
+
+    /// A cached version of the playhead.
+    cached_playhead: Cell<Option<Duration>>,
+}
+
+impl Timeline {
+    /// Creates a new, empty timeline using the specified state.
+    pub fn new(state: Entity<TimelineState>) -> Self {
+        Self {
+            duration: Duration::ZERO,
+            previous_start: Duration::ZERO,
+            essential: false,
+            state,
+            cached_playhead: Cell::new(None),
+        }
+    }
+
+    /// Sets whether the timeline is essential and should play even when the
+    /// app-wide [`MotionSettings`] reduce motion. The time scale still applies.
+    pub fn essential(mut self, essential: bool) -> Self {
+        self.essential = essential;
+        self
+    }
+
+    /// Adds a tween that starts after every track added so far has ended.
+    pub fn then<T: Lerp + Clone + 'static>(&mut self, tween: Tween<T>) -> Track<T> {
+        self.at(self.duration, tween)
+    }
+
+    /// Adds a tween that starts together with the previously added tween.
+    pub fn alongside<T: Lerp + Clone + 'static>(&mut self, tween: Tween<T>) -> Track<T> {
+        self.at(self.previous_start, tween)
+    }
+
+    /// Adds a tween that starts at the given time on the timeline.
+    pub fn at<T: Lerp + Clone + 'static>(&mut self, start: Duration, tween: Tween<T>) -> Track<T> {
+        let track = Track {
+            start: start + tween.delay,
+            tween,
+        };
+
+        self.previous_start = start;
+        self.duration = self.duration.max(track.end());
+        self.cached_playhead.set(None);
+
+        track
+    }
+
+    /// Returns the total duration of the timeline, which is when its last track ends.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Returns the speed at which the playhead should move under the current
+    /// motion settings. Reduced motion speeds the timeline up to fit in the
+    /// shortened duration, or makes it infinitely fast to snap to the end.
+    fn rate(&self, cx: &App) -> f32 {
+        MotionSettings::get(cx)
+            .playback_rate(self.duration.as_secs_f32(), self.essential)
+            .unwrap_or(f32::INFINITY)
+    }
+
+    /// Brings the state up to date with the motion settings, and stops the
+    /// playhead once it has reached its end.
+    fn sync_state(&self, cx: &mut App) {
+        let duration = self.duration;
+        let rate = self.rate(cx);
+
+        let state = self.state.read(cx);
+        if state.rate != rate || state.has_finished(duration) {
+            self.state.update(cx, |state, _cx| {
+                state.set_rate(rate, duration);
+                state.settle(duration);
+            });
+        }
+    }
+
+    /// Evaluates and returns the value of the given track at the playhead.
+    ///
+    /// While the timeline is playing, this requests animation frames until the
+    /// playhead reaches the end (or the start, when reversed). The playhead is
+    /// cached for the duration of the current frame, so every track is evaluated
+    /// at the same time.
+    pub fn evaluate<T: Lerp + Clone + 'static>(
+        &self,
+        track: &Track<T>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> T {
+        let playhead = match self.cached_playhead.get() {
+            Some(playhead) => playhead,
+            None => {
+                self.sync_state(cx);
+
+                let state = self.state.read(cx);
+                state.clock.begin_frame(window);
+
+                let playhead = state.playhead(self.duration);
+
+                // A frozen timeline doesn't move, so it doesn't need animation frames.
+                if state.rate > 0. && state.is_moving(playhead, self.duration) {
+                    window.request_animation_frame();
+                }
+
+                self.cached_playhead.set(Some(playhead));
+                playhead
+            }
+        };
+
+        track.value_at(playhead)
+    }
+
+    /// Returns the current position of the playhead.
+    pub fn playhead(&self, cx: &App) -> Duration {
+        self.state.read(cx).playhead(self.duration)
+    }
+
+    /// Returns the position of the playhead as a fraction of the timeline's
+    /// duration, between 0 and 1.
+    pub fn progress(&self, cx: &App) -> f32 {
+        if self.duration.is_zero() {
+            return 1.;
+        }
+
+        self.playhead(cx).as_secs_f32() / self.duration.as_secs_f32()
+    }
+
+    /// Returns whether the playhead is moving.
+    pub fn is_playing(&self, cx: &App) -> bool {
+        let state = self.state.read(cx);
+        state.is_moving(state.playhead(self.duration), self.duration)
+    }
+
+    fn set_playback(&self, cx: &mut App, position: Option<Duration>, playback: Playback) {
+        let duration = self.duration;
+        self.sync_state(cx);
+
+        self.state.update(cx, |state, cx| {
+            let position = position.unwrap_or_else(|| state.playhead(duration));
+            state.restart(position.min(duration), playback);
+            cx.notify();
+        });
+        self.cached_playhead.set(None);
+    }
+
+    /// Plays the timeline forwards from the playhead. If the playhead is at the
+    /// end, the timeline plays again from the start.
+    pub fn play(&self, cx: &mut App) {
+        let at_end = self.playhead(cx) >= self.duration;
+        self.set_playback(cx, at_end.then_some(Duration::ZERO), Playback::Forward);
+    }
+
+    /// Plays the timeline backwards from the playhead, towards the start. If the
+    /// playhead is at the start, the timeline plays backwards from the end.
+    pub fn reverse(&self, cx: &mut App) {
+        let at_start = self.playhead(cx).is_zero();
+        self.set_playback(cx, at_start.then_some(self.duration), Playback::Reverse);
+    }
+
+    /// Stops the playhead where it is.
+    pub fn pause(&self, cx: &mut App) {
+        self.set_playback(cx, None, Playback::Paused);
+    }
+
+    /// Moves the playhead to the given time. If the timeline is playing, it
+    /// carries on playing from there. Otherwise, including when it has already
+    /// played to its end, it stays paused there.
+    pub fn seek(&self, cx: &mut App, time: Duration) {
+        let duration = self.duration;
+        let state = self.state.read(cx);
+
+        let playback = if state.is_moving(state.playhead(duration), duration) {
+            state.playback
+        } else {
+            Playback::Paused
+        };
+        self.set_playback(cx, Some(time), playback);
+    }
+
+    /// Replaces the clock that this timeline's state reads the current time from.
+    pub fn set_clock(&self, cx: &mut App, clock: impl Clock + 'static) {
+        let duration = self.duration;
+
+        self.state.update(cx, |state, _cx| {
+            let position = state.playhead(duration);
+            state.clock = Rc::new(clock);
+            state.restart(position, state.playback);
+        });
+        self.cached_playhead.set(None);
+    }
+
+    /// Returns the entity ID associated with this timeline's state.
+    pub fn entity_id(&self) -> EntityId {
+        self.state.entity_id()
+    }
+}
+
+#[cfg(all(test, feature = "test-support"))]
+mod tests {
+    use super::*;
+    use crate::{ManualClock, ReducedMotion};
+    use gpui::{AppContext, TestAppContext};
+
+    fn create_timeline(cx: &mut App, clock: &ManualClock) -> Timeline {
+        let state = cx.new(|_| TimelineState::new().with_clock(clock.clone()));
+        Timeline::new(state)
+    }
+
+    fn millis(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[gpui::test]
+    fn test_timeline_placement(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let clock = ManualClock::new();
+            let mut timeline = create_timeline(cx, &clock);
+
+            let panel = timeline.then(Tween::new(0.0_f32, 1.0, millis(300)));
+            let backdrop = timeline.alongside(Tween::new(0.0_f32, 1.0, millis(200)));
+            let contents = timeline.then(Tween::new(0.0_f32, 1.0, millis(100)));
+            let delayed =
+                timeline.alongside(Tween::new(0.0_f32, 1.0, millis(100)).with_delay(millis(50)));
+            let fixed = timeline.at(millis(100), Tween::new(0.0_f32, 1.0, millis(100)));
+
+            assert_eq!((panel.start(), panel.end()), (millis(0), millis(300)));
+            assert_eq!((backdrop.start(), backdrop.end()), (millis(0), millis(200)));
+            assert_eq!(
+                (contents.start(), contents.end()),
+                (millis(300), millis(400))
+            );
+            assert_eq!((delayed.start(), delayed.end()), (millis(350), millis(450)));
+            assert_eq!((fixed.start(), fixed.end()), (millis(100), millis(200)));
+            assert_eq!(timeline.duration(), millis(450));
+        });
+    }
+
+    fn value_at(timeline: &Timeline, track: &Track<f32>, cx: &App) -> f32 {
+        track.value_at(timeline.playhead(cx))
+    }
+
+    #[gpui::test]
+    fn test_timeline_play(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let clock = ManualClock::new();
+            let mut timeline = create_timeline(cx, &clock);
+            let first = timeline.then(Tween::new(0.0_f32, 100.0, millis(100)));
+            let second = timeline.then(Tween::new(0.0_f32, 10.0, millis(100)));
+
+            // Timelines start paused at the beginning.
+            clock.advance(millis(50));
+            assert!(!timeline.is_playing(cx));
+            assert_eq!(value_at(&timeline, &first, cx), 0.0);
+
+            timeline.play(cx);
+            assert!(timeline.is_playing(cx));
+
+            clock.advance(millis(50));
+            assert_eq!(value_at(&timeline, &first, cx), 50.0);
+            assert_eq!(value_at(&timeline, &second, cx), 0.0);
+
+            clock.advance(millis(100));
+            assert_eq!(value_at(&timeline, &first, cx), 100.0);
+            assert_eq!(value_at(&timeline, &second, cx), 5.0);
+
+            clock.advance(millis(100));
+            assert_eq!(timeline.playhead(cx), millis(200));
+            assert_eq!(value_at(&timeline, &second, cx), 10.0);
+            assert!(!timeline.is_playing(cx));
+        });
+    }
+
+    #[gpui::test]
+    fn test_timeline_reverse_pause_and_seek(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let clock = ManualClock::new();
+            let mut timeline = create_timeline(cx, &clock);
+            let track = timeline.then(Tween::new(0.0_f32, 100.0, millis(200)));
+
+            // Reversing from the start plays backwards from the end.
+            timeline.reverse(cx);
+            clock.advance(millis(50));
+            assert_eq!(value_at(&timeline, &track, cx), 75.0);
+
+            timeline.pause(cx);
+            clock.advance(millis(50));
+            assert_eq!(value_at(&timeline, &track, cx), 75.0);
+            assert!(!timeline.is_playing(cx));
+
+            timeline.seek(cx, millis(50));
+            assert_eq!(value_at(&timeline, &track, cx), 25.0);
+            assert_eq!(timeline.progress(cx), 0.25);
+
+            // A playing timeline keeps playing after seeking.
+            timeline.play(cx);
+            timeline.seek(cx, millis(100));
+            clock.advance(millis(50));
+            assert_eq!(value_at(&timeline, &track, cx), 75.0);
+
+            // Playing from the end starts over.
+            clock.advance(millis(100));
+            timeline.play(cx);
+            assert_eq!(value_at(&timeline, &track, cx), 0.0);
+        });
+    }
+
+    #[gpui::test]
+    fn test_timeline_stays_at_end(cx: &mut TestAppContext) {
+        let cx = cx.add_empty_window();
+
+        cx.update(|window, cx| {
+            let clock = ManualClock::new();
+            let mut timeline = create_timeline(cx, &clock);
+            let track = timeline.then(Tween::new(0.0_f32, 100.0, millis(200)));
+
+            // Seeking a timeline that has played to its end doesn't play it again.
+            timeline.play(cx);
+            clock.advance(millis(300));
+            timeline.seek(cx, millis(100));
+            clock.advance(millis(50));
+            assert!(!timeline.is_playing(cx));
+            assert_eq!(value_at(&timeline, &track, cx), 50.0);
+
+            // Once played to its end, the playhead stays there when tracks are added.
+            timeline.play(cx);
+            clock.advance(millis(150));
+            assert_eq!(timeline.evaluate(&track, window, cx), 100.0);
+
+            timeline.then(Tween::new(0.0_f32, 1.0, millis(100)));
+            assert_eq!(timeline.playhead(cx), millis(200));
+            assert!(!timeline.is_playing(cx));
+        });
+    }
+
+    #[gpui::test]
+    fn test_timeline_follows_motion_settings(cx: &mut TestAppContext) {
+        let clock = ManualClock::new();
+        let mut timeline = cx.update(|cx| create_timeline(cx, &clock));
+        let track = timeline.then(Tween::new(0.0_f32, 100.0, millis(200)));
+
+        cx.update(|cx| {
+            // At half speed, the playhead moves 50ms in 100ms.
+            MotionSettings::set(MotionSettings::default().with_time_scale(0.5), cx);
+            timeline.play(cx);
+            clock.advance(millis(100));
+            assert_eq!(value_at(&timeline, &track, cx), 25.0);
+
+            // Reduced motion snaps to the end, in the direction of playback.
+            MotionSettings::set(
+                MotionSettings::default().with_reduced_motion(Some(ReducedMotion::Snap)),
+                cx,
+            );
+            timeline.reverse(cx);
+            assert_eq!(value_at(&timeline, &track, cx), 0.0);
+            assert!(!timeline.is_playing(cx));
+
+            // Essential timelines play at their own pace.
+            let essential = timeline.clone().essential(true);
+            essential.play(cx);
+            clock.advance(millis(100));
+            assert_eq!(value_at(&essential, &track, cx), 50.0);
+
+            // Shortened timelines speed up to fit.
+            MotionSettings::set(
+                MotionSettings::default()
+                    .with_reduced_motion(Some(ReducedMotion::Shorten(millis(50)))),
+                cx,
+            );
+            timeline.seek(cx, millis(0));
+            timeline.play(cx);
+            clock.advance(millis(25));
+            assert_eq!(value_at(&timeline, &track, cx), 50.0);
+        });
+    }
+}
@@ -4,12 +4,21 @@ use std::time::Duration;
 
 use gpui::{App, Context, ElementId, Window};
 
-use crate::{Lerp, Transition, TransitionState};
+use crate::{
+    Components, GestureState, GestureTransition, KeyframeState, KeyframeTransition, Keyframes,
+    Lerp, SpringState, SpringTransition, Timeline, TimelineState, Transition, TransitionGroup,
+    TransitionGroupState, TransitionState,
+};
 
 /// Extension trait for GPUI's [`Window`] that provides convenient methods for creating transitions.
 ///
 /// This trait adds `use_transition` and `use_keyed_transition` methods to `Window`,
 /// allowing you to create animated transitions that integrate with GPUI's state management.
+/// Spring-driven transitions are available through `use_spring` and `use_keyed_spring`,
+/// keyframe tracks through `use_keyframes` and `use_keyed_keyframes`, staggered
+/// groups through `use_transition_group` and `use_keyed_transition_group`,
+/// drag-driven transitions through `use_gesture` and `use_keyed_gesture`, and
+/// timelines through `use_timeline` and `use_keyed_timeline`.
 ///
 /// # Example
 ///
@@ -79,6 +88,213 @@ pub trait WindowUseTransition {
         duration: Duration,
         initial_goal: impl Fn(&mut Window, &mut Context<TransitionState<T>>) -> T,
     ) -> Transition<T>;
+
+    /// Creates a new spring transition with automatic state management.
+    ///
+    /// The state for this spring is managed internally and will be recreated
+    /// on each render. For persistent state across renders, use [`use_keyed_spring`](Self::use_keyed_spring).
+    ///
+    /// # Arguments
+    ///
+    /// * `cx` - The GPUI application context.
+    /// * `initial_goal` - A closure that returns the initial value for the spring.
+    ///
+    /// # Returns
+    ///
+    /// A [`SpringTransition`] that can be used to animate values.
+    fn use_spring<T: Components + Clone + PartialEq + 'static>(
+        &mut self,
+        cx: &mut App,
+        initial_goal: impl Fn(&mut Window, &mut Context<SpringState<T>>) -> T,
+    ) -> SpringTransition<T>;
+
+    /// Creates a new keyed spring transition with persistent state.
+    ///
+    /// The state for this spring is associated with the provided key and will
+    /// persist across renders as long as the key remains the same.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - A unique identifier for this spring's state. Can be a string,
+    ///   number, or any type that implements `Into<ElementId>`.
+    /// * `cx` - The GPUI application context.
+    /// * `initial_goal` - A closure that returns the initial value for the spring.
+    ///   This is only called when the state is first created.
+    ///
+    /// # Returns
+    ///
+    /// A [`SpringTransition`] that can be used to animate values.
+    fn use_keyed_spring<T: Components + Clone + PartialEq + 'static>(
+        &mut self,
+        key: impl Into<ElementId>,
+        cx: &mut App,
+        initial_goal: impl Fn(&mut Window, &mut Context<SpringState<T>>) -> T,
+    ) -> SpringTransition<T>;
+
+    /// Creates a new keyframe transition with automatic state management.
+    ///
+    /// The state for this transition is managed internally and will be recreated
+    /// on each render. For persistent state across renders, use [`use_keyed_keyframes`](Self::use_keyed_keyframes).
+    ///
+    /// # Arguments
+    ///
+    /// * `cx` - The GPUI application context.
+    /// * `duration` - How long it takes to play the whole track.
+    /// * `initial_keyframes` - A closure that returns the initial track.
+    ///
+    /// # Returns
+    ///
+    /// A [`KeyframeTransition`] that can be used to play keyframe tracks.
+    fn use_keyframes<T: Lerp + Clone + PartialEq + 'static>(
+        &mut self,
+        cx: &mut App,
+        duration: Duration,
+        initial_keyframes: impl Fn(&mut Window, &mut Context<KeyframeState<T>>) -> Keyframes<T>,
+    ) -> KeyframeTransition<T>;
+
+    /// Creates a new keyed keyframe transition with persistent state.
+    ///
+    /// The state for this transition is associated with the provided key and will
+    /// persist across renders as long as the key remains the same.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - A unique identifier for this transition's state. Can be a string,
+    ///   number, or any type that implements `Into<ElementId>`.
+    /// * `cx` - The GPUI application context.
+    /// * `duration` - How long it takes to play the whole track.
+    /// * `initial_keyframes` - A closure that returns the initial track.
+    ///   This is only called when the state is first created.
+    ///
+    /// # Returns
+    ///
+    /// A [`KeyframeTransition`] that can be used to play keyframe tracks.
+    fn use_keyed_keyframes<T: Lerp + Clone + PartialEq + 'static>(
+        &mut self,
+        key: impl Into<ElementId>,
+        cx: &mut App,
+        duration: Duration,
+        initial_keyframes: impl Fn(&mut Window, &mut Context<KeyframeState<T>>) -> Keyframes<T>,
+    ) -> KeyframeTransition<T>;
+
+    /// Creates a new transition group with automatic state management.
+    ///
+    /// The state for this group is managed internally and will be recreated
+    /// on each render. For persistent state across renders, use [`use_keyed_transition_group`](Self::use_keyed_transition_group).
+    ///
+    /// # Arguments
+    ///
+    /// * `cx` - The GPUI application context.
+    /// * `duration` - How long each item's transition should take to complete.
+    /// * `initial_goal` - A closure that returns the initial goal shared by every item.
+    ///
+    /// # Returns
+    ///
+    /// A [`TransitionGroup`] that can be used to animate many items.
+    fn use_transition_group<T: Lerp + Clone + PartialEq + 'static>(
+        &mut self,
+        cx: &mut App,
+        duration: Duration,
+        initial_goal: impl Fn(&mut Window, &mut Context<TransitionGroupState<T>>) -> T,
+    ) -> TransitionGroup<T>;
+
+    /// Creates a new keyed transition group with persistent state.
+    ///
+    /// The state for this group is associated with the provided key and will
+    /// persist across renders as long as the key remains the same.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - A unique identifier for this group's state. Can be a string,
+    ///   number, or any type that implements `Into<ElementId>`.
+    /// * `cx` - The GPUI application context.
+    /// * `duration` - How long each item's transition should take to complete.
+    /// * `initial_goal` - A closure that returns the initial goal shared by every item.
+    ///   This is only called when the state is first created.
+    ///
+    /// # Returns
+    ///
+    /// A [`TransitionGroup`] that can be used to animate many items.
+    fn use_keyed_transition_group<T: Lerp + Clone + PartialEq + 'static>(
+        &mut self,
+        key: impl Into<ElementId>,
+        cx: &mut App,
+        duration: Duration,
+        initial_goal: impl Fn(&mut Window, &mut Context<TransitionGroupState<T>>) -> T,
+    ) -> TransitionGroup<T>;
+
+    /// Creates a new gesture transition with automatic state management.
+    ///
+    /// The state for this gesture is managed internally and will be recreated
+    /// on each render. For persistent state across renders, use [`use_keyed_gesture`](Self::use_keyed_gesture).
+    ///
+    /// # Arguments
+    ///
+    /// * `cx` - The GPUI application context.
+    /// * `initial_progress` - A closure that returns the initial progress.
+    ///
+    /// # Returns
+    ///
+    /// A [`GestureTransition`] that can be driven by a drag.
+    fn use_gesture(
+        &mut self,
+        cx: &mut App,
+        initial_progress: impl Fn(&mut Window, &mut Context<GestureState>) -> f32,
+    ) -> GestureTransition;
+
+    /// Creates a new keyed gesture transition with persistent state.
+    ///
+    /// The state for this gesture is associated with the provided key and will
+    /// persist across renders as long as the key remains the same.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - A unique identifier for this gesture's state. Can be a string,
+    ///   number, or any type that implements `Into<ElementId>`.
+    /// * `cx` - The GPUI application context.
+    /// * `initial_progress` - A closure that returns the initial progress.
+    ///   This is only called when the state is first created.
+    ///
+    /// # Returns
+    ///
+    /// A [`GestureTransition`] that can be driven by a drag.
+    fn use_keyed_gesture(
+        &mut self,
+        key: impl Into<ElementId>,
+        cx: &mut App,
+        initial_progress: impl Fn(&mut Window, &mut Context<GestureState>) -> f32,
+    ) -> GestureTransition;
+
+    /// Creates a new, empty timeline with automatic state management.
+    ///
+    /// The state for this timeline is managed internally and will be recreated
+    /// on each render. For persistent state across renders, use [`use_keyed_timeline`](Self::use_keyed_timeline).
+    ///
+    /// # Arguments
+    ///
+    /// * `cx` - The GPUI application context.
+    ///
+    /// # Returns
+    ///
+    /// A [`Timeline`] that tweens can be added to.
+    fn use_timeline(&mut self, cx: &mut App) -> Timeline;
+
+    /// Creates a new, empty keyed timeline with persistent state.
+    ///
+    /// The state for this timeline, including its playhead, is associated with
+    /// the provided key and will persist across renders as long as the key
+    /// remains the same.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - A unique identifier for this timeline's state. Can be a string,
+    ///   number, or any type that implements `Into<ElementId>`.
+    /// * `cx` - The GPUI application context.
+    ///
+    /// # Returns
+    ///
+    /// A [`Timeline`] that tweens can be added to.
+    fn use_keyed_timeline(&mut self, key: impl Into<ElementId>, cx: &mut App) -> Timeline;
 }
 
 impl WindowUseTransition for Window {
@@ -105,4 +321,107 @@ impl WindowUseTransition for Window {
 
         Transition::new(state, duration)
     }
+
+    fn use_spring<T: Components + Clone + PartialEq + 'static>(
+        &mut self,
+        cx: &mut App,
+        init: impl Fn(&mut Window, &mut Context<SpringState<T>>) -> T,
+    ) -> SpringTransition<T> {
+        let state = self.use_state(cx, |window, cx| SpringState::new(init(window, cx)));
+
+        SpringTransition::new(state)
+    }
+
+    fn use_keyed_spring<T: Components + Clone + PartialEq + 'static>(
+        &mut self,
+        key: impl Into<ElementId>,
+        cx: &mut App,
+        init: impl Fn(&mut Window, &mut Context<SpringState<T>>) -> T,
+    ) -> SpringTransition<T> {
+        let state = self.use_keyed_state(key, cx, |window, cx| SpringState::new(init(window, cx)));
+
+        SpringTransition::new(state)
+    }
+
+    fn use_keyframes<T: Lerp + Clone + PartialEq + 'static>(
+        &mut self,
+        cx: &mut App,
+        duration: Duration,
+        init: impl Fn(&mut Window, &mut Context<KeyframeState<T>>) -> Keyframes<T>,
+    ) -> KeyframeTransition<T> {
+        let state = self.use_state(cx, |window, cx| KeyframeState::new(init(window, cx)));
+
+        KeyframeTransition::new(state, duration)
+    }
+
+    fn use_keyed_keyframes<T: Lerp + Clone + PartialEq + 'static>(
+        &mut self,
+        key: impl Into<ElementId>,
+        cx: &mut App,
+        duration: Duration,
+        init: impl Fn(&mut Window, &mut Context<KeyframeState<T>>) -> Keyframes<T>,
+    ) -> KeyframeTransition<T> {
+        let state =
+            self.use_keyed_state(key, cx, |window, cx| KeyframeState::new(init(window, cx)));
+
+        KeyframeTransition::new(state, duration)
+    }
+
+    fn use_transition_group<T: Lerp + Clone + PartialEq + 'static>(
+        &mut self,
+        cx: &mut App,
+        duration: Duration,
+        init: impl Fn(&mut Window, &mut Context<TransitionGroupState<T>>) -> T,
+    ) -> TransitionGroup<T> {
+        let state = self.use_state(cx, |window, cx| TransitionGroupState::new(init(window, cx)));
+
+        TransitionGroup::new(state, duration)
+    }
+
+    fn use_keyed_transition_group<T: Lerp + Clone + PartialEq + 'static>(
+        &mut self,
+        key: impl Into<ElementId>,
+        cx: &mut App,
+        duration: Duration,
+        init: impl Fn(&mut Window, &mut Context<TransitionGroupState<T>>) -> T,
+    ) -> TransitionGroup<T> {
+        let state = self.use_keyed_state(key, cx, |window, cx| {
+            TransitionGroupState::new(init(window, cx))
+        });
+
+        TransitionGroup::new(state, duration)
+    }
+
+    fn use_gesture(
+        &mut self,
+        cx: &mut App,
+        init: impl Fn(&mut Window, &mut Context<GestureState>) -> f32,
+    ) -> GestureTransition {
+        let state = self.use_state(cx, |window, cx| GestureState::new(init(window, cx)));
+
+        GestureTransition::new(state)
+    }
+
+    fn use_keyed_gesture(
+        &mut self,
+        key: impl Into<ElementId>,
+        cx: &mut App,
+        init: impl Fn(&mut Window, &mut Context<GestureState>) -> f32,
+    ) -> GestureTransition {
+        let state = self.use_keyed_state(key, cx, |window, cx| GestureState::new(init(window, cx)));
+
+        GestureTransition::new(state)
+    }
+
+    fn use_timeline(&mut self, cx: &mut App) -> Timeline {
+        let state = self.use_state(cx, |_window, _cx| TimelineState::new());
+
+        Timeline::new(state)
+    }
+
+    fn use_keyed_timeline(&mut self, key: impl Into<ElementId>, cx: &mut App) -> Timeline {
+        let state = self.use_keyed_state(key, cx, |_window, _cx| TimelineState::new());
+
+        Timeline::new(state)
+    }
 }
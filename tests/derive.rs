@@ -0,0 +1,152 @@
+#![cfg(feature = "derive")]
+
+use gpui::{Pixels, Point, Rgba, px};
+use gpui_transitions::Lerp;
+
+#[derive(Debug, Clone, PartialEq, Lerp)]
+struct Style {
+    opacity: f32,
+    offset: Point<Pixels>,
+    color: Rgba,
+}
+
+#[derive(Debug, Clone, PartialEq, Lerp)]
+struct Pair(f32, i32);
+
+#[derive(Debug, Clone, PartialEq, Lerp)]
+struct Unit;
+
+#[derive(Debug, Clone, PartialEq)]
+struct Label(&'static str);
+
+#[derive(Debug, Clone, PartialEq, Lerp)]
+struct WithAttributes {
+    width: f32,
+    #[lerp(skip)]
+    label: Label,
+    #[lerp(snap)]
+    visible: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Lerp)]
+struct Generic<T> {
+    value: T,
+    #[lerp(snap)]
+    tag: &'static str,
+}
+
+#[derive(Debug, Clone, PartialEq, Lerp)]
+enum Shape {
+    Circle { radius: f32 },
+    Square(f32),
+    Empty,
+}
+
+#[test]
+fn test_derive_named_struct() {
+    let start = Style {
+        opacity: 0.0,
+        offset: Point {
+            x: px(0.0),
+            y: px(10.0),
+        },
+        color: Rgba {
+            r: 0.0,
+            g: 0.0,
+            b: 0.0,
+            a: 1.0,
+        },
+    };
+    let end = Style {
+        opacity: 1.0,
+        offset: Point {
+            x: px(100.0),
+            y: px(30.0),
+        },
+        color: Rgba {
+            r: 1.0,
+            g: 0.5,
+            b: 0.0,
+            a: 1.0,
+        },
+    };
+
+    let mid = start.lerp(&end, 0.5);
+    assert_eq!(mid.opacity, 0.5);
+    assert_eq!(
+        mid.offset,
+        Point {
+            x: px(50.0),
+            y: px(20.0)
+        }
+    );
+    assert_eq!(mid.color.g, 0.25);
+
+    assert_eq!(start.lerp(&end, 0.0), start);
+    assert_eq!(start.lerp(&end, 1.0), end);
+}
+
+#[test]
+fn test_derive_tuple_and_unit_structs() {
+    assert_eq!(Pair(0.0, 0).lerp(&Pair(10.0, 100), 0.5), Pair(5.0, 50));
+    assert_eq!(Unit.lerp(&Unit, 0.5), Unit);
+}
+
+#[test]
+fn test_derive_skip_and_snap() {
+    let start = WithAttributes {
+        width: 0.0,
+        label: Label("start"),
+        visible: false,
+    };
+    let end = WithAttributes {
+        width: 100.0,
+        label: Label("end"),
+        visible: true,
+    };
+
+    let early = start.lerp(&end, 0.25);
+    assert_eq!(early.width, 25.0);
+    assert_eq!(early.label, Label("end"));
+    assert!(!early.visible);
+
+    let late = start.lerp(&end, 0.75);
+    assert_eq!(late.width, 75.0);
+    assert!(late.visible);
+}
+
+#[test]
+fn test_derive_generic_struct() {
+    let start = Generic {
+        value: 0.0_f64,
+        tag: "a",
+    };
+    let end = Generic {
+        value: 4.0_f64,
+        tag: "b",
+    };
+
+    assert_eq!(
+        start.lerp(&end, 0.5),
+        Generic {
+            value: 2.0,
+            tag: "b"
+        }
+    );
+}
+
+#[test]
+fn test_derive_enum() {
+    let small = Shape::Circle { radius: 0.0 };
+    let large = Shape::Circle { radius: 10.0 };
+    assert_eq!(small.lerp(&large, 0.5), Shape::Circle { radius: 5.0 });
+
+    assert_eq!(
+        Shape::Square(2.0).lerp(&Shape::Square(4.0), 0.5),
+        Shape::Square(3.0)
+    );
+
+    // Different variants snap halfway through.
+    assert_eq!(small.lerp(&Shape::Empty, 0.4), small);
+    assert_eq!(small.lerp(&Shape::Empty, 0.6), Shape::Empty);
+}